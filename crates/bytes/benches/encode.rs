@@ -0,0 +1,52 @@
+use std::{hint::black_box, io};
+
+use bytes::{Bytes, derive_deftly_template_Bytes};
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use derive_deftly::Deftly;
+
+// Mirrors `graph::Edge`, which can't be used here without a circular dependency.
+#[derive(Deftly, Clone, Copy)]
+#[derive_deftly(Bytes)]
+struct Edge(usize, usize);
+
+const LEN: usize = 10_000;
+
+fn bench<T>(c: &mut Criterion, name: &str, value: &T)
+where
+    T: Bytes,
+{
+    let mut group = c.benchmark_group(name);
+    group.throughput(Throughput::Bytes(value.required_size() as u64));
+
+    group.bench_function("to_bytes", |b| b.iter(|| black_box(value).to_bytes()));
+    group.bench_function("write_into", |b| {
+        let mut buf = Vec::new();
+        b.iter(|| {
+            buf.clear();
+            black_box(value).write_into(&mut buf);
+        })
+    });
+    group.bench_function("write_to", |b| {
+        b.iter(|| black_box(value).write_to(io::sink()))
+    });
+
+    let bytes = value.to_bytes();
+    group.bench_function("from_bytes", |b| {
+        b.iter(|| T::from_bytes(black_box(&bytes)))
+    });
+
+    group.finish();
+}
+
+fn edges(c: &mut Criterion) {
+    let edges: Vec<_> = (0..LEN).map(|i| Edge(i, (i * 7) % LEN)).collect();
+    bench(c, "Vec<Edge>", &edges);
+}
+
+fn hashes(c: &mut Criterion) {
+    let hashes: Vec<_> = (0..LEN).map(|i| [i as u8; 32]).collect();
+    bench(c, "Vec<[u8; 32]>", &hashes);
+}
+
+criterion_group!(benches, edges, hashes);
+criterion_main!(benches);
@@ -0,0 +1,54 @@
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::{Bytes, BytesError, ReadLimits};
+
+// Like `Bytes::write_to` and `Bytes::read_from`, these treat the whole stream as a single encoded
+// value. The value is encoded and decoded in memory, so only the I/O is asynchronous.
+pub trait AsyncBytes: Bytes {
+    fn write_async<W>(&self, w: &mut W) -> impl Future<Output = std::io::Result<()>> + Send
+    where
+        Self: Sync,
+        W: AsyncWrite + Unpin + Send,
+    {
+        async move {
+            let bytes = self.to_bytes();
+            w.write_all(&bytes).await?;
+            w.flush().await
+        }
+    }
+
+    fn read_async<R>(r: &mut R) -> impl Future<Output = Result<Self, BytesError>> + Send
+    where
+        Self: Sized,
+        R: AsyncRead + Unpin + Send,
+    {
+        Self::read_async_with_limits(r, ReadLimits::default())
+    }
+
+    // The stream is buffered before it's decoded, so the buffer counts towards `max_alloc`, and
+    // reading stops once a stream turns out to be longer than that.
+    fn read_async_with_limits<R>(
+        r: &mut R,
+        limits: ReadLimits,
+    ) -> impl Future<Output = Result<Self, BytesError>> + Send
+    where
+        Self: Sized,
+        R: AsyncRead + Unpin + Send,
+    {
+        async move {
+            let max_alloc = u64::try_from(limits.max_alloc).unwrap_or(u64::MAX);
+            let mut bytes = Vec::new();
+            r.take(max_alloc.saturating_add(1))
+                .read_to_end(&mut bytes)
+                .await
+                .map_err(BytesError::Io)?;
+            if bytes.len() > limits.max_alloc {
+                return Err(BytesError::LimitExceeded(limits.max_alloc));
+            }
+
+            Self::from_bytes_with_limits(&bytes, limits)
+        }
+    }
+}
+
+impl<T> AsyncBytes for T where T: Bytes + ?Sized {}
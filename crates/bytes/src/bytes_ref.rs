@@ -0,0 +1,69 @@
+use std::str;
+
+use crate::{BytesError, BytesReader};
+
+pub trait BytesRef<'a>: Sized {
+    fn from_bytes_ref(bytes: &'a [u8]) -> Result<Self, BytesError> {
+        let mut reader = BytesReader::new(bytes);
+        let output = Self::read_ref(&mut reader)?;
+        reader.finish()?;
+        Ok(output)
+    }
+
+    fn read_ref(reader: &mut BytesReader<'a>) -> Result<Self, BytesError>;
+}
+
+impl<'a> BytesRef<'a> for &'a [u8] {
+    fn read_ref(reader: &mut BytesReader<'a>) -> Result<Self, BytesError> {
+        let len = reader.read_len::<()>()?;
+        reader.read_borrowed::<Self>(len)
+    }
+}
+
+impl<'a, const N: usize> BytesRef<'a> for &'a [[u8; N]] {
+    fn read_ref(reader: &mut BytesReader<'a>) -> Result<Self, BytesError> {
+        let len = reader.read_len::<()>()?;
+        let size = len.checked_mul(N).ok_or(BytesError::UsizeTooSmall)?;
+        let bytes = reader.read_borrowed::<Self>(size)?;
+
+        // SAFETY: `[u8; N]` has the same alignment as `u8`, and `bytes` holds exactly `len` arrays
+        // of `N` bytes each.
+        #[cfg(not(feature = "forbid-unsafe"))]
+        return Ok(unsafe { std::slice::from_raw_parts(bytes.as_ptr().cast(), len) });
+
+        // Zero-sized arrays can't be split into chunks, but leaking a vector of them doesn't
+        // allocate:
+        #[cfg(feature = "forbid-unsafe")]
+        if N == 0 {
+            Ok(Vec::leak(vec![[0; N]; len]))
+        } else {
+            Ok(bytes.as_chunks().0)
+        }
+    }
+}
+
+impl<'a> BytesRef<'a> for &'a str {
+    fn read_ref(reader: &mut BytesReader<'a>) -> Result<Self, BytesError> {
+        let len = reader.read_len::<()>()?;
+        let pos = reader.read;
+        let bytes = reader.read_borrowed::<Self>(len)?;
+        str::from_utf8(bytes).map_err(|_| BytesError::InvalidUtf8(pos))
+    }
+}
+
+impl<'a, T> BytesRef<'a> for Vec<T>
+where
+    T: BytesRef<'a>,
+{
+    fn read_ref(reader: &mut BytesReader<'a>) -> Result<Self, BytesError> {
+        let len = reader.read_len::<T>()?;
+        // Every element takes at least a byte, so the remaining data bounds how many there are:
+        let capacity = len.min(reader.remaining().unwrap_or(0));
+        let mut elems = Vec::with_capacity(capacity);
+        for _ in 0..len {
+            elems.push(T::read_ref(reader)?);
+        }
+
+        Ok(elems)
+    }
+}
@@ -0,0 +1,81 @@
+use std::io::{self, Read};
+
+use flate2::{Compression, read::DeflateDecoder, write::DeflateEncoder};
+
+use crate::{Bytes, BytesError, BytesReader, BytesWriter};
+
+// Encodes the inner value, then compresses it with deflate. The uncompressed length is stored in
+// front of the compressed data, so that readers can account for it against their limits before
+// decompressing, and can't be made to inflate more data than that.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Compressed<T>(pub T);
+
+// How many times larger than the compressed data the buffer for the inflated data is to begin with:
+const INITIAL_RATIO: usize = 4;
+
+fn compress(bytes: &[u8]) -> Vec<u8> {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+    io::Write::write_all(&mut encoder, bytes).expect("writing to a vector cannot fail");
+    encoder.finish().expect("writing to a vector cannot fail")
+}
+
+impl<T> Bytes for Compressed<T>
+where
+    T: Bytes,
+{
+    // Computing the size requires compressing the value, so `to_bytes` and `write_into` are
+    // overridden to compress it only once, without asking for the size up front. As a field of
+    // another value, it's still compressed once for the size and once more when written.
+    fn required_size(&self) -> usize {
+        8 + 8 + compress(&self.0.to_bytes()).len()
+    }
+
+    fn to_bytes(&self) -> Box<[u8]> {
+        let mut buf = Vec::new();
+        self.write_into(&mut buf);
+        buf.into()
+    }
+
+    fn write_into(&self, buf: &mut Vec<u8>) {
+        let mut writer = BytesWriter::appending(buf);
+        self.write(&mut writer);
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        let bytes = self.0.to_bytes();
+        let compressed = compress(&bytes);
+
+        (bytes.len() as u64).write(writer);
+        (compressed.len() as u64).write(writer);
+        writer.write(&compressed);
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        let len = reader.read_len::<u8>()?;
+        let compressed_len = reader.read_len::<u8>()?;
+        let pos = reader.read;
+        let compressed = reader.read_vec::<Self>(compressed_len)?;
+
+        // The uncompressed length is untrusted until the data has been inflated, so the buffer
+        // starts out proportional to the compressed data and grows as the data actually inflates:
+        let mut bytes = Vec::with_capacity(len.min(compressed_len.saturating_mul(INITIAL_RATIO)));
+        DeflateDecoder::new(&compressed[..])
+            .take((len as u64).saturating_add(1))
+            .read_to_end(&mut bytes)
+            .map_err(|_| BytesError::InvalidCompression(pos))?;
+        if bytes.len() != len {
+            return Err(BytesError::InvalidCompression(pos));
+        }
+
+        // The inner value shares the limits of the outer reader, including what it has already
+        // allocated and how deeply it is nested:
+        let mut inner = BytesReader::with_limits(&bytes, reader.limits);
+        inner.allocated = reader.allocated;
+        inner.depth = reader.depth;
+        let output = T::read(&mut inner)?;
+        reader.allocated = inner.allocated;
+        inner.finish()?;
+
+        Ok(Self(output))
+    }
+}
@@ -0,0 +1,629 @@
+use std::{
+    any,
+    borrow::Cow,
+    collections::{BTreeSet, HashSet},
+    convert::Infallible,
+    marker::PhantomData,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    rc::Rc,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use derive_deftly::define_derive_deftly;
+
+#[cfg(feature = "deflate")]
+use crate::Compressed;
+#[cfg(feature = "crypto")]
+use crate::Sealed;
+use crate::{CanonicalNan, Envelope, Packed, VarInt, VarLen, ZigZag};
+
+pub trait Describe {
+    fn describe() -> Layout;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Layout {
+    // A little-endian integer, or a type that is encoded as one.
+    Primitive {
+        name: &'static str,
+        size: usize,
+    },
+    VarInt,
+    // A `VarInt` of a zigzag-mapped signed integer.
+    ZigZag,
+    Array {
+        elem: Box<Layout>,
+        len: usize,
+    },
+    Sequence {
+        len: Box<Layout>,
+        elem: Box<Layout>,
+    },
+    String {
+        len: Box<Layout>,
+    },
+    PackedArray {
+        elem: Box<Layout>,
+        len: usize,
+        bits: u32,
+    },
+    PackedSequence {
+        len: Box<Layout>,
+        elem: Box<Layout>,
+        bits: u32,
+    },
+    Option(Box<Layout>),
+    Tuple(Vec<Layout>),
+    Struct {
+        name: &'static str,
+        fields: Vec<Field>,
+    },
+    Tagged {
+        name: &'static str,
+        version: u8,
+        fields: Vec<Field>,
+    },
+    Tlv {
+        name: &'static str,
+        fields: Vec<Field>,
+    },
+    Enum {
+        name: &'static str,
+        discriminant: Box<Layout>,
+        variants: Vec<Variant>,
+    },
+    Envelope {
+        magic: u32,
+        version: u8,
+        inner: Box<Layout>,
+    },
+    Compressed {
+        inner: Box<Layout>,
+    },
+    // The nonce and the authenticated ciphertext of the inner encoding.
+    Sealed {
+        inner: Box<Layout>,
+    },
+    // A field encoded with a custom codec, whose layout isn't known.
+    Custom {
+        codec: &'static str,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Field {
+    pub name: &'static str,
+    pub ty: &'static str,
+    pub tag: Option<u16>,
+    pub layout: Layout,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Variant {
+    pub name: &'static str,
+    pub discriminant: u128,
+    pub fields: Vec<Field>,
+}
+
+impl Layout {
+    pub fn fixed_size(&self) -> Option<usize> {
+        match self {
+            Layout::Primitive { size, .. } => Some(*size),
+            Layout::Array { elem, len } => elem.fixed_size().map(|size| size * len),
+            Layout::PackedArray { len, bits, .. } => Some((len * *bits as usize).div_ceil(8)),
+            Layout::Tuple(elems) => elems.iter().map(Layout::fixed_size).sum(),
+            Layout::Struct { fields, .. } => {
+                fields.iter().map(|field| field.layout.fixed_size()).sum()
+            }
+            Layout::Envelope { inner, .. } => inner.fixed_size().map(|size| size + 4 + 1),
+            Layout::VarInt
+            | Layout::ZigZag
+            | Layout::Sequence { .. }
+            | Layout::String { .. }
+            | Layout::PackedSequence { .. }
+            | Layout::Option(_)
+            | Layout::Tagged { .. }
+            | Layout::Tlv { .. }
+            | Layout::Enum { .. }
+            | Layout::Compressed { .. }
+            | Layout::Sealed { .. }
+            | Layout::Custom { .. } => None,
+        }
+    }
+}
+
+impl Field {
+    fn new<T>(name: &'static str) -> Self
+    where
+        T: Describe,
+    {
+        Self {
+            name,
+            ty: any::type_name::<T>(),
+            tag: None,
+            layout: T::describe(),
+        }
+    }
+}
+
+fn length_prefix() -> Box<Layout> {
+    Box::new(u64::describe())
+}
+
+macro_rules! impl_describe_for_primitive {
+    ($($ty:ty => $name:literal),+) => {
+        $(
+            impl Describe for $ty {
+                fn describe() -> Layout {
+                    Layout::Primitive {
+                        name: $name,
+                        size: size_of::<$ty>(),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_describe_for_primitive!(u8 => "u8", u16 => "u16", u32 => "u32", u64 => "u64", u128 => "u128");
+impl_describe_for_primitive!(i8 => "i8", i16 => "i16", i32 => "i32", i64 => "i64", i128 => "i128");
+impl_describe_for_primitive!(f32 => "f32", f64 => "f64", bool => "bool", char => "char");
+
+impl Describe for usize {
+    fn describe() -> Layout {
+        u64::describe()
+    }
+}
+
+impl Describe for isize {
+    fn describe() -> Layout {
+        i64::describe()
+    }
+}
+
+impl<T> Describe for CanonicalNan<T>
+where
+    T: Describe,
+{
+    fn describe() -> Layout {
+        T::describe()
+    }
+}
+
+impl Describe for Duration {
+    fn describe() -> Layout {
+        Layout::Struct {
+            name: "Duration",
+            fields: vec![Field::new::<u64>("secs"), Field::new::<u32>("nanos")],
+        }
+    }
+}
+
+impl Describe for SystemTime {
+    fn describe() -> Layout {
+        Layout::Struct {
+            name: "SystemTime",
+            fields: vec![Field::new::<i64>("secs"), Field::new::<u32>("nanos")],
+        }
+    }
+}
+
+impl Describe for Ipv4Addr {
+    fn describe() -> Layout {
+        <[u8; 4]>::describe()
+    }
+}
+
+impl Describe for Ipv6Addr {
+    fn describe() -> Layout {
+        <[u8; 16]>::describe()
+    }
+}
+
+impl Describe for IpAddr {
+    fn describe() -> Layout {
+        Layout::Enum {
+            name: "IpAddr",
+            discriminant: Box::new(u8::describe()),
+            variants: vec![
+                Variant {
+                    name: "V4",
+                    discriminant: 0,
+                    fields: vec![Field::new::<Ipv4Addr>("0")],
+                },
+                Variant {
+                    name: "V6",
+                    discriminant: 1,
+                    fields: vec![Field::new::<Ipv6Addr>("0")],
+                },
+            ],
+        }
+    }
+}
+
+impl Describe for SocketAddrV4 {
+    fn describe() -> Layout {
+        Layout::Struct {
+            name: "SocketAddrV4",
+            fields: vec![Field::new::<Ipv4Addr>("ip"), Field::new::<u16>("port")],
+        }
+    }
+}
+
+impl Describe for SocketAddrV6 {
+    fn describe() -> Layout {
+        Layout::Struct {
+            name: "SocketAddrV6",
+            fields: vec![
+                Field::new::<Ipv6Addr>("ip"),
+                Field::new::<u16>("port"),
+                Field::new::<u32>("flowinfo"),
+                Field::new::<u32>("scope_id"),
+            ],
+        }
+    }
+}
+
+impl Describe for SocketAddr {
+    fn describe() -> Layout {
+        Layout::Enum {
+            name: "SocketAddr",
+            discriminant: Box::new(u8::describe()),
+            variants: vec![
+                Variant {
+                    name: "V4",
+                    discriminant: 0,
+                    fields: vec![Field::new::<SocketAddrV4>("0")],
+                },
+                Variant {
+                    name: "V6",
+                    discriminant: 1,
+                    fields: vec![Field::new::<SocketAddrV6>("0")],
+                },
+            ],
+        }
+    }
+}
+
+macro_rules! impl_describe_for_tuple {
+    ($($t:ident),+) => {
+        impl<$($t),+> Describe for ($($t),+)
+        where
+            $($t: Describe,)+
+        {
+            fn describe() -> Layout {
+                Layout::Tuple(vec![$($t::describe()),+])
+            }
+        }
+    };
+}
+
+impl_describe_for_tuple!(T1, T2);
+impl_describe_for_tuple!(T1, T2, T3);
+impl_describe_for_tuple!(T1, T2, T3, T4);
+impl_describe_for_tuple!(T1, T2, T3, T4, T5);
+impl_describe_for_tuple!(T1, T2, T3, T4, T5, T6);
+impl_describe_for_tuple!(T1, T2, T3, T4, T5, T6, T7);
+impl_describe_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8);
+impl_describe_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9);
+impl_describe_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+impl_describe_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+impl_describe_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
+
+impl<const N: usize, T> Describe for [T; N]
+where
+    T: Describe,
+{
+    fn describe() -> Layout {
+        Layout::Array {
+            elem: Box::new(T::describe()),
+            len: N,
+        }
+    }
+}
+
+macro_rules! impl_describe_for_sequence {
+    ($($ty:ty),+) => {
+        $(
+            impl<T> Describe for $ty
+            where
+                T: Describe,
+            {
+                fn describe() -> Layout {
+                    Layout::Sequence {
+                        len: length_prefix(),
+                        elem: Box::new(T::describe()),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_describe_for_sequence!(Box<[T]>, Vec<T>, BTreeSet<T>);
+
+impl<T, S> Describe for HashSet<T, S>
+where
+    T: Describe,
+{
+    fn describe() -> Layout {
+        Vec::<T>::describe()
+    }
+}
+
+impl<T> Describe for Cow<'_, [T]>
+where
+    T: Describe + Clone,
+{
+    fn describe() -> Layout {
+        Vec::<T>::describe()
+    }
+}
+
+macro_rules! impl_describe_for_pointer {
+    ($ptr:ident) => {
+        impl<T> Describe for $ptr<T>
+        where
+            T: Describe,
+        {
+            fn describe() -> Layout {
+                T::describe()
+            }
+        }
+    };
+}
+
+impl_describe_for_pointer!(Box);
+impl_describe_for_pointer!(Rc);
+impl_describe_for_pointer!(Arc);
+
+impl Describe for () {
+    fn describe() -> Layout {
+        Layout::Tuple(Vec::new())
+    }
+}
+
+impl<T> Describe for PhantomData<T>
+where
+    T: ?Sized,
+{
+    fn describe() -> Layout {
+        Layout::Tuple(Vec::new())
+    }
+}
+
+impl Describe for Infallible {
+    fn describe() -> Layout {
+        Layout::Enum {
+            name: "Infallible",
+            discriminant: Box::new(u8::describe()),
+            variants: Vec::new(),
+        }
+    }
+}
+
+impl<T> Describe for Option<T>
+where
+    T: Describe,
+{
+    fn describe() -> Layout {
+        Layout::Option(Box::new(T::describe()))
+    }
+}
+
+macro_rules! impl_describe_for_string {
+    ($($ty:ty),+) => {
+        $(
+            impl Describe for $ty {
+                fn describe() -> Layout {
+                    Layout::String {
+                        len: length_prefix(),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_describe_for_string!(String, Box<str>, Cow<'_, str>);
+
+impl<T> Describe for VarInt<T> {
+    fn describe() -> Layout {
+        Layout::VarInt
+    }
+}
+
+impl<T> Describe for ZigZag<T> {
+    fn describe() -> Layout {
+        Layout::ZigZag
+    }
+}
+
+impl<T> Describe for VarLen<Vec<T>>
+where
+    T: Describe,
+{
+    fn describe() -> Layout {
+        Layout::Sequence {
+            len: Box::new(Layout::VarInt),
+            elem: Box::new(T::describe()),
+        }
+    }
+}
+
+impl<T> Describe for VarLen<Box<[T]>>
+where
+    T: Describe,
+{
+    fn describe() -> Layout {
+        VarLen::<Vec<T>>::describe()
+    }
+}
+
+impl Describe for VarLen<String> {
+    fn describe() -> Layout {
+        Layout::String {
+            len: Box::new(Layout::VarInt),
+        }
+    }
+}
+
+impl<const N: usize, T, const BITS: u32> Describe for Packed<[T; N], BITS>
+where
+    T: Describe,
+{
+    fn describe() -> Layout {
+        Layout::PackedArray {
+            elem: Box::new(T::describe()),
+            len: N,
+            bits: BITS,
+        }
+    }
+}
+
+impl<T, const BITS: u32> Describe for Packed<Vec<T>, BITS>
+where
+    T: Describe,
+{
+    fn describe() -> Layout {
+        Layout::PackedSequence {
+            len: length_prefix(),
+            elem: Box::new(T::describe()),
+            bits: BITS,
+        }
+    }
+}
+
+impl<T, const BITS: u32> Describe for Packed<Box<[T]>, BITS>
+where
+    T: Describe,
+{
+    fn describe() -> Layout {
+        Packed::<Vec<T>, BITS>::describe()
+    }
+}
+
+impl<T, const MAGIC: u32, const VERSION: u8> Describe for Envelope<T, MAGIC, VERSION>
+where
+    T: Describe,
+{
+    fn describe() -> Layout {
+        Layout::Envelope {
+            magic: MAGIC,
+            version: VERSION,
+            inner: Box::new(T::describe()),
+        }
+    }
+}
+
+#[cfg(feature = "deflate")]
+impl<T> Describe for Compressed<T>
+where
+    T: Describe,
+{
+    fn describe() -> Layout {
+        Layout::Compressed {
+            inner: Box::new(T::describe()),
+        }
+    }
+}
+
+#[cfg(feature = "crypto")]
+impl<T> Describe for Sealed<T>
+where
+    T: Describe,
+{
+    fn describe() -> Layout {
+        Layout::Sealed {
+            inner: Box::new(T::describe()),
+        }
+    }
+}
+
+define_derive_deftly! {
+    export Describe:
+
+    ${defcond SKIPPED any(
+        fmeta(bytes(skip)),
+        all(fmeta(bytes(default)), not(tmeta(bytes(tagged)))),
+    )}
+    ${define REPR {
+        ${if tmeta(bytes(repr)) { ${tmeta(bytes(repr)) as ty} } else { u8 }}
+    }}
+    ${define FIELD_LAYOUT {
+        ${if fmeta(bytes(with)) {
+            $crate::describe::Layout::Custom {
+                codec: stringify!(${fmeta(bytes(with)) as path}),
+            }
+        } else {
+            <$ftype as $crate::Describe>::describe()
+        }}
+    }}
+    ${define FIELD {
+        $crate::describe::Field {
+            name: stringify!($fname),
+            ty: ::std::any::type_name::<$ftype>(),
+            tag: None,
+            layout: $FIELD_LAYOUT,
+        }
+    }}
+
+    impl<$tgens> $crate::Describe for $ttype
+    where
+        $twheres
+        $(${when not(any(SKIPPED, fmeta(bytes(with))))} $ftype: $crate::Describe,)
+    {
+        #[allow(unused)]
+        fn describe() -> $crate::describe::Layout {
+            ${if any(tmeta(bytes(tagged)), tmeta(bytes(tlv))) {
+                let mut fields = Vec::new();
+                let mut tag: u16 = 0;
+                ${for fields {
+                    ${when not(SKIPPED)}
+                    ${if fmeta(bytes(tag)) {
+                        tag = ${fmeta(bytes(tag)) as expr};
+                    }}
+                    fields.push($crate::describe::Field { tag: Some(tag), ..$FIELD });
+                    tag += 1;
+                }}
+                ${if tmeta(bytes(tlv)) {
+                    $crate::describe::Layout::Tlv {
+                        name: stringify!($tname),
+                        fields,
+                    }
+                } else {
+                    $crate::describe::Layout::Tagged {
+                        name: stringify!($tname),
+                        version: ${if tmeta(bytes(version)) { ${tmeta(bytes(version)) as expr} } else { 0 }},
+                        fields,
+                    }
+                }}
+            } else if is_enum {
+                let mut variants = Vec::new();
+                let mut discriminant: $REPR = 0;
+                ${for variants {
+                    ${if vmeta(bytes(discriminant)) {
+                        discriminant = ${vmeta(bytes(discriminant)) as expr};
+                    }}
+                    variants.push($crate::describe::Variant {
+                        name: stringify!($vname),
+                        discriminant: discriminant as u128,
+                        fields: vec![${for fields { ${when not(SKIPPED)} $FIELD, }}],
+                    });
+                    discriminant = discriminant.wrapping_add(1);
+                }}
+                $crate::describe::Layout::Enum {
+                    name: stringify!($tname),
+                    discriminant: Box::new(<$REPR as $crate::Describe>::describe()),
+                    variants,
+                }
+            } else {
+                $crate::describe::Layout::Struct {
+                    name: stringify!($tname),
+                    fields: vec![${for fields { ${when not(SKIPPED)} $FIELD, }}],
+                }
+            }}
+        }
+    }
+}
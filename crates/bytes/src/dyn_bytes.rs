@@ -0,0 +1,43 @@
+use std::io;
+
+use crate::{Bytes, BytesWriter};
+
+// An object-safe view of the writing half of `Bytes`, which itself can't be made into a trait
+// object because of its associated constants and `read`. Every `Bytes` type implements it, so
+// values of different types can be encoded through a `Box<dyn DynBytes>`.
+pub trait DynBytes {
+    fn required_size_dyn(&self) -> usize;
+    fn write_dyn(&self, writer: &mut BytesWriter);
+
+    fn to_bytes_dyn(&self) -> Box<[u8]> {
+        let mut writer = BytesWriter::new(self.required_size_dyn());
+        self.write_dyn(&mut writer);
+        writer.finish()
+    }
+
+    fn write_into_dyn(&self, buf: &mut Vec<u8>) {
+        buf.reserve(self.required_size_dyn());
+        let mut writer = BytesWriter::appending(buf);
+        self.write_dyn(&mut writer);
+    }
+
+    fn write_to_dyn(&self, w: &mut dyn io::Write) -> io::Result<()> {
+        let mut writer = BytesWriter::streaming(w);
+        self.write_dyn(&mut writer);
+        writer.finish_streaming()?;
+        w.flush()
+    }
+}
+
+impl<T> DynBytes for T
+where
+    T: Bytes,
+{
+    fn required_size_dyn(&self) -> usize {
+        self.required_size()
+    }
+
+    fn write_dyn(&self, writer: &mut BytesWriter) {
+        self.write(writer);
+    }
+}
@@ -0,0 +1,15 @@
+// Helpers for the enum encoding generated by the `Bytes` derive template.
+
+pub const fn check_discriminants(discriminants: &[u128]) {
+    let mut i = 0;
+    while i < discriminants.len() {
+        let mut j = i + 1;
+        while j < discriminants.len() {
+            if discriminants[i] == discriminants[j] {
+                panic!("enum has duplicate discriminants");
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+}
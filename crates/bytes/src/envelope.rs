@@ -0,0 +1,59 @@
+use crate::{Bytes, BytesError, BytesReader, BytesRef, BytesWriter, FixedBytes, fixed};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Envelope<T, const MAGIC: u32, const VERSION: u8>(pub T);
+
+fn read_header<const MAGIC: u32, const VERSION: u8>(
+    reader: &mut BytesReader,
+) -> Result<(), BytesError> {
+    let magic = u32::read(reader)?;
+    if magic != MAGIC {
+        return Err(BytesError::InvalidMagic(magic));
+    }
+
+    let version = u8::read(reader)?;
+    if version != VERSION {
+        return Err(BytesError::UnsupportedVersion(version));
+    }
+
+    Ok(())
+}
+
+impl<T, const MAGIC: u32, const VERSION: u8> Bytes for Envelope<T, MAGIC, VERSION>
+where
+    T: Bytes,
+{
+    const FIXED_SIZE: Option<usize> = fixed::sum(&[u32::FIXED_SIZE, u8::FIXED_SIZE, T::FIXED_SIZE]);
+
+    fn required_size(&self) -> usize {
+        MAGIC.required_size() + VERSION.required_size() + self.0.required_size()
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        MAGIC.write(writer);
+        VERSION.write(writer);
+        self.0.write(writer);
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        read_header::<MAGIC, VERSION>(reader)?;
+        T::read(reader).map(Self)
+    }
+}
+
+impl<T, const MAGIC: u32, const VERSION: u8> FixedBytes for Envelope<T, MAGIC, VERSION>
+where
+    T: FixedBytes,
+{
+    const SIZE: usize = fixed::size::<Self>();
+}
+
+impl<'a, T, const MAGIC: u32, const VERSION: u8> BytesRef<'a> for Envelope<T, MAGIC, VERSION>
+where
+    T: BytesRef<'a>,
+{
+    fn read_ref(reader: &mut BytesReader<'a>) -> Result<Self, BytesError> {
+        read_header::<MAGIC, VERSION>(reader)?;
+        T::read_ref(reader).map(Self)
+    }
+}
@@ -0,0 +1,140 @@
+use std::{
+    marker::PhantomData,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6},
+    rc::Rc,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use derive_deftly::define_derive_deftly;
+
+use crate::{Bytes, CanonicalNan};
+
+pub trait FixedBytes: Bytes {
+    const SIZE: usize;
+}
+
+// Const helpers for computing `Bytes::FIXED_SIZE`, which is `None` as soon as any part of a type
+// has a variable size.
+
+pub const fn sum(sizes: &[Option<usize>]) -> Option<usize> {
+    let mut total = 0;
+    let mut i = 0;
+    while i < sizes.len() {
+        match sizes[i] {
+            Some(size) => total += size,
+            None => return None,
+        }
+        i += 1;
+    }
+    Some(total)
+}
+
+pub const fn product(size: Option<usize>, count: usize) -> Option<usize> {
+    match size {
+        Some(size) => Some(size * count),
+        None => None,
+    }
+}
+
+// `Bytes::FIXED_SIZE` is the single source of truth, so that the two can never disagree.
+pub const fn size<T>() -> usize
+where
+    T: Bytes + ?Sized,
+{
+    match T::FIXED_SIZE {
+        Some(size) => size,
+        None => panic!("type does not have a fixed size"),
+    }
+}
+
+macro_rules! impl_fixed_bytes {
+    ($($ty:ty),+) => {
+        $(
+            impl FixedBytes for $ty {
+                const SIZE: usize = size::<Self>();
+            }
+        )+
+    };
+}
+
+impl_fixed_bytes!(u8, u16, u32, u64, u128, usize);
+impl_fixed_bytes!(i8, i16, i32, i64, i128, isize);
+impl_fixed_bytes!(f32, f64, CanonicalNan<f32>, CanonicalNan<f64>);
+impl_fixed_bytes!(bool, char, (), Duration, SystemTime);
+impl_fixed_bytes!(Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6);
+
+macro_rules! impl_fixed_bytes_for_tuple {
+    ($($t:ident),+) => {
+        impl<$($t),+> FixedBytes for ($($t),+)
+        where
+            $($t: FixedBytes,)+
+        {
+            const SIZE: usize = size::<Self>();
+        }
+    };
+}
+
+impl_fixed_bytes_for_tuple!(T1, T2);
+impl_fixed_bytes_for_tuple!(T1, T2, T3);
+impl_fixed_bytes_for_tuple!(T1, T2, T3, T4);
+impl_fixed_bytes_for_tuple!(T1, T2, T3, T4, T5);
+impl_fixed_bytes_for_tuple!(T1, T2, T3, T4, T5, T6);
+impl_fixed_bytes_for_tuple!(T1, T2, T3, T4, T5, T6, T7);
+impl_fixed_bytes_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8);
+impl_fixed_bytes_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9);
+impl_fixed_bytes_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+impl_fixed_bytes_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+impl_fixed_bytes_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
+
+impl<const N: usize, T> FixedBytes for [T; N]
+where
+    T: FixedBytes,
+{
+    const SIZE: usize = size::<Self>();
+}
+
+macro_rules! impl_fixed_bytes_for_pointer {
+    ($ptr:ident) => {
+        impl<T> FixedBytes for $ptr<T>
+        where
+            T: FixedBytes,
+        {
+            const SIZE: usize = size::<Self>();
+        }
+    };
+}
+
+impl_fixed_bytes_for_pointer!(Box);
+impl_fixed_bytes_for_pointer!(Rc);
+impl_fixed_bytes_for_pointer!(Arc);
+
+impl<T> FixedBytes for PhantomData<T>
+where
+    T: ?Sized,
+{
+    const SIZE: usize = size::<Self>();
+}
+
+define_derive_deftly! {
+    export FixedBytes:
+
+    ${if any(not(is_struct), tmeta(bytes(tagged)), tmeta(bytes(tlv))) {
+        ${error "fixed size encoding is only supported for untagged structs"}
+    }}
+    ${for fields {
+        ${if fmeta(bytes(with)) {
+            ${error "fields with custom codecs do not have a fixed size"}
+        }}
+    }}
+    ${defcond SKIPPED any(fmeta(bytes(skip)), fmeta(bytes(default)))}
+
+    impl<$tgens> $crate::FixedBytes for $ttype
+    where
+        $twheres
+        Self: $crate::Bytes,
+        $(${when not(SKIPPED)} $ftype: $crate::FixedBytes,)
+    {
+        const SIZE: usize = $crate::fixed::size::<Self>();
+    }
+}
@@ -0,0 +1,158 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    marker::PhantomData,
+};
+
+use arbitrary::{Arbitrary, Error, Result, Unstructured};
+
+#[cfg(feature = "base64")]
+use crate::Base64Bytes;
+#[cfg(feature = "deflate")]
+use crate::Compressed;
+#[cfg(feature = "serde")]
+use crate::SerdeAsBytes;
+use crate::{
+    Bytes, CanonicalNan, Envelope, HexBytes, Packed, VarInt, VarLen, ZigZag, packed::PackedElem,
+};
+
+// Encodings of arbitrary values, which are mutated in the same ways as `testing::mutations`, so
+// that fuzz targets reach both the successful and the failing paths of a decoder instead of
+// spending most of their time on inputs that fail on the first length prefix.
+pub struct Encoded<T> {
+    pub bytes: Vec<u8>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Encoded<T>
+where
+    T: Bytes,
+{
+    pub fn decode(&self) -> Option<T> {
+        T::from_bytes_lossy(&self.bytes)
+    }
+}
+
+impl<T> Debug for Encoded<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("Encoded").field(&self.bytes).finish()
+    }
+}
+
+impl<'a, T> Arbitrary<'a> for Encoded<T>
+where
+    T: Arbitrary<'a> + Bytes,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut bytes = T::arbitrary(u)?.to_bytes().into_vec();
+        match u.choose_index(4)? {
+            0 => {}
+            1 => {
+                let len = u.int_in_range(0..=bytes.len())?;
+                bytes.truncate(len);
+            }
+            2 => bytes.extend(Vec::<u8>::arbitrary(u)?),
+            _ => {
+                if !bytes.is_empty() {
+                    let index = u.choose_index(bytes.len())?;
+                    bytes[index] ^= u.int_in_range(1..=u8::MAX)?;
+                }
+            }
+        }
+
+        Ok(Self {
+            bytes,
+            marker: PhantomData,
+        })
+    }
+}
+
+// Wrappers generate their inner value, so that types using them can derive `Arbitrary`:
+
+macro_rules! impl_arbitrary_for_wrapper {
+    ($wrapper:ident) => {
+        impl<'a, T> Arbitrary<'a> for $wrapper<T>
+        where
+            T: Arbitrary<'a>,
+        {
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                T::arbitrary(u).map(Self)
+            }
+
+            fn size_hint(depth: usize) -> (usize, Option<usize>) {
+                T::size_hint(depth)
+            }
+        }
+    };
+}
+
+impl_arbitrary_for_wrapper!(VarInt);
+impl_arbitrary_for_wrapper!(VarLen);
+impl_arbitrary_for_wrapper!(ZigZag);
+impl_arbitrary_for_wrapper!(CanonicalNan);
+impl_arbitrary_for_wrapper!(HexBytes);
+#[cfg(feature = "base64")]
+impl_arbitrary_for_wrapper!(Base64Bytes);
+#[cfg(feature = "deflate")]
+impl_arbitrary_for_wrapper!(Compressed);
+#[cfg(feature = "serde")]
+impl_arbitrary_for_wrapper!(SerdeAsBytes);
+
+impl<'a, T, const MAGIC: u32, const VERSION: u8> Arbitrary<'a> for Envelope<T, MAGIC, VERSION>
+where
+    T: Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        T::arbitrary(u).map(Self)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        T::size_hint(depth)
+    }
+}
+
+// Packed elements are generated within `BITS` bits, since larger values can't be encoded:
+fn arbitrary_packed_elem<T, const BITS: u32>(u: &mut Unstructured) -> Result<T>
+where
+    T: PackedElem,
+{
+    let mask = u64::MAX.checked_shr(u64::BITS - BITS).unwrap_or(0);
+    T::from_bits(u64::arbitrary(u)? & mask).map_err(|_| Error::IncorrectFormat)
+}
+
+impl<'a, T, const BITS: u32> Arbitrary<'a> for Packed<Vec<T>, BITS>
+where
+    T: PackedElem,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let len = u.arbitrary_len::<u64>()?;
+        (0..len)
+            .map(|_| arbitrary_packed_elem::<T, BITS>(u))
+            .collect::<Result<_>>()
+            .map(Self)
+    }
+}
+
+impl<'a, T, const BITS: u32> Arbitrary<'a> for Packed<Box<[T]>, BITS>
+where
+    T: PackedElem,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Packed::<Vec<T>, BITS>::arbitrary(u).map(|elems| Self(elems.0.into_boxed_slice()))
+    }
+}
+
+impl<'a, const N: usize, T, const BITS: u32> Arbitrary<'a> for Packed<[T; N], BITS>
+where
+    T: PackedElem,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let elems = (0..N)
+            .map(|_| arbitrary_packed_elem::<T, BITS>(u))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self(elems.try_into().unwrap_or_else(|_| unreachable!())))
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (N * size_of::<u64>(), Some(N * size_of::<u64>()))
+    }
+}
@@ -1,27 +1,151 @@
+#![cfg_attr(feature = "forbid-unsafe", forbid(unsafe_code))]
+
+#[cfg(not(feature = "forbid-unsafe"))]
+use std::mem::{self, MaybeUninit};
 use std::{
+    borrow::Cow,
+    collections::{BTreeSet, HashSet},
+    convert::Infallible,
     error::Error,
     fmt::{self, Display, Formatter},
-    mem::{self, MaybeUninit},
+    hash::{BuildHasher, Hash},
+    io,
+    marker::PhantomData,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    rc::Rc,
+    sync::Arc,
+    time::{Duration, SystemTime},
 };
 
 #[doc(hidden)]
 pub use derive_deftly;
 use derive_deftly::define_derive_deftly;
 
+#[cfg(feature = "tokio")]
+pub use async_io::AsyncBytes;
+pub use bytes_ref::BytesRef;
+#[cfg(feature = "deflate")]
+pub use compressed::Compressed;
+pub use describe::Describe;
+pub use dyn_bytes::DynBytes;
+pub use envelope::Envelope;
+pub use fixed::FixedBytes;
+pub use packed::Packed;
+#[cfg(feature = "rayon")]
+pub use par::ParFromBytes;
+#[cfg(feature = "crypto")]
+pub use sealed::Sealed;
+#[cfg(feature = "serde")]
+pub use serde_bridge::SerdeAsBytes;
+#[cfg(feature = "base64")]
+pub use text::Base64Bytes;
+pub use text::HexBytes;
+pub use varint::{VarInt, VarLen, ZigZag};
+
+#[cfg(feature = "tokio")]
+mod async_io;
+mod bytes_ref;
+#[cfg(feature = "deflate")]
+mod compressed;
+pub mod describe;
+mod dyn_bytes;
+#[doc(hidden)]
+pub mod enums;
+mod envelope;
+#[doc(hidden)]
+pub mod fixed;
+#[cfg(feature = "arbitrary")]
+pub mod fuzz;
+pub mod packed;
+#[cfg(feature = "rayon")]
+mod par;
+#[cfg(feature = "crypto")]
+pub mod sealed;
+#[cfg(feature = "serde")]
+mod serde_bridge;
+#[doc(hidden)]
+pub mod tagged;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod text;
+pub mod tlv;
+pub mod varint;
+
 derive_deftly::template_export_semver_check!("1.0.1");
 
 pub trait Bytes {
+    // The encoded size of every value of this type, if it is known statically. Containers use it
+    // to compute sizes and validate lengths without visiting each element.
+    const FIXED_SIZE: Option<usize> = None;
+
+    // The wire type of this type's payload when it is a field of a TLV-encoded struct.
+    #[doc(hidden)]
+    const WIRE_TYPE: tlv::WireType = tlv::WireType::of(Self::FIXED_SIZE);
+
     fn to_bytes(&self) -> Box<[u8]> {
         let mut writer = BytesWriter::new(self.required_size());
         self.write(&mut writer);
         writer.finish()
     }
 
+    fn write_into(&self, buf: &mut Vec<u8>) {
+        buf.reserve(self.required_size());
+        let mut writer = BytesWriter::appending(buf);
+        self.write(&mut writer);
+    }
+
+    fn write_to<W>(&self, w: W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        let mut w = w;
+        let mut writer = BytesWriter::streaming(&mut w);
+        self.write(&mut writer);
+        writer.finish_streaming()?;
+        io::Write::flush(&mut w)
+    }
+
     fn from_bytes(bytes: &[u8]) -> Result<Self, BytesError>
     where
         Self: Sized,
     {
-        let mut reader = BytesReader::new(bytes);
+        Self::from_bytes_with_limits(bytes, ReadLimits::default())
+    }
+
+    fn from_bytes_with_limits(bytes: &[u8], limits: ReadLimits) -> Result<Self, BytesError>
+    where
+        Self: Sized,
+    {
+        let mut reader = BytesReader::with_limits(bytes, limits);
+        let output = Self::read(&mut reader)?;
+        reader.finish()?;
+        Ok(output)
+    }
+
+    // Entry point for fuzz targets, which only care about panics: the error is discarded and
+    // allocations are capped, so that arbitrary input can't abort the process.
+    fn from_bytes_lossy(bytes: &[u8]) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        Self::from_bytes_with_limits(bytes, ReadLimits::proportional(bytes.len())).ok()
+    }
+
+    fn read_from<R>(r: R) -> Result<Self, BytesError>
+    where
+        R: io::Read,
+        Self: Sized,
+    {
+        Self::read_from_with_limits(r, ReadLimits::default())
+    }
+
+    fn read_from_with_limits<R>(r: R, limits: ReadLimits) -> Result<Self, BytesError>
+    where
+        R: io::Read,
+        Self: Sized,
+    {
+        let mut r = io::BufReader::new(r);
+        let mut reader = BytesReader::streaming(&mut r, limits);
         let output = Self::read(&mut reader)?;
         reader.finish()?;
         Ok(output)
@@ -32,134 +156,1190 @@ pub trait Bytes {
     fn read(reader: &mut BytesReader) -> Result<Self, BytesError>
     where
         Self: Sized;
+
+    // Hooks that let runs of bytes be copied in a single call instead of element by element. Only
+    // `u8` overrides them, which keeps the fast path free of unsafe casts.
+
+    #[doc(hidden)]
+    fn as_byte_slice(_elems: &[Self]) -> Option<&[u8]>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
+    #[doc(hidden)]
+    fn read_byte_vec(
+        _reader: &mut BytesReader,
+        _len: usize,
+    ) -> Option<Result<Vec<Self>, BytesError>>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
+    #[doc(hidden)]
+    fn read_byte_array<const N: usize>(
+        _reader: &mut BytesReader,
+    ) -> Option<Result<[Self; N], BytesError>>
+    where
+        Self: Sized,
+    {
+        None
+    }
 }
 
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum BytesError {
-    EndOfData(usize),
+    // The data ended while reading `ty`, which needed `needed` more bytes at `pos`. Streams don't
+    // know how much data is left, so `available` is only known for slices.
+    EndOfData {
+        pos: usize,
+        needed: usize,
+        available: Option<usize>,
+        ty: &'static str,
+    },
     TrailingData(usize),
     UsizeTooSmall,
+    InvalidBool(u8),
+    InvalidChar(u32),
+    InvalidUtf8(usize),
+    InvalidOptionTag(u8),
+    DuplicateElement(usize),
+    InvalidDiscriminant(u64),
+    InvalidVarInt(usize),
+    Io(io::Error),
+    BorrowUnsupported(usize),
+    RewindUnsupported(usize),
+    NonCanonicalNan(usize),
+    InvalidTime(usize),
+    UnsupportedVersion(u8),
+    InvalidMagic(u32),
+    // A field of a tagged struct without a default is missing.
+    MissingField,
+    // A field of a tagged struct appears more than once, starting at `pos`.
+    DuplicateField(usize),
+    FieldLengthMismatch {
+        pos: usize,
+        expected: usize,
+        actual: usize,
+    },
+    LimitExceeded(usize),
+    InvalidHex(usize),
+    InvalidBase64(usize),
+    NonZeroPadding(usize),
+    InvalidCompression(usize),
+    InvalidSeal,
+    InvalidWireType(usize),
+    DepthExceeded(usize),
+    // The element at `index` of a `Packed` sequence doesn't fit in `bits` bits.
+    ValueTooWide {
+        index: usize,
+        bits: u32,
+    },
+    // A decoded value was rejected by its `validate` function.
+    Invalid {
+        pos: usize,
+        source: Box<dyn Error + Send + Sync>,
+    },
+    InField {
+        ty: &'static str,
+        field: &'static str,
+        source: Box<BytesError>,
+    },
 }
 
-impl Error for BytesError {}
+impl BytesError {
+    pub fn in_field(self, ty: &'static str, field: &'static str) -> Self {
+        Self::InField {
+            ty,
+            field,
+            source: Box::new(self),
+        }
+    }
+
+    pub fn path(&self) -> Vec<(&'static str, &'static str)> {
+        let mut path = Vec::new();
+        let mut err = self;
+        while let Self::InField { ty, field, source } = err {
+            path.push((*ty, *field));
+            err = source;
+        }
+        path
+    }
+}
+
+impl Error for BytesError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Invalid { source, .. } => Some(&**source),
+            Self::InField { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
 
 impl Display for BytesError {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
-            Self::EndOfData(pos) => write!(f, "end of data at position {pos}"),
+            Self::EndOfData {
+                pos,
+                needed,
+                available: Some(available),
+                ty,
+            } => write!(
+                f,
+                "end of data at position {pos} while reading `{ty}`: {needed} bytes needed, \
+                 {available} available"
+            ),
+            Self::EndOfData {
+                pos, needed, ty, ..
+            } => write!(
+                f,
+                "end of data at position {pos} while reading `{ty}`: {needed} bytes needed"
+            ),
             Self::TrailingData(pos) => write!(f, "trailing data at position {pos}"),
             Self::UsizeTooSmall => write!(f, "data could not fit into `usize`"),
+            Self::InvalidBool(value) => write!(f, "invalid `bool` value {value}"),
+            Self::InvalidChar(value) => write!(f, "invalid `char` value {value:#x}"),
+            Self::InvalidUtf8(pos) => write!(f, "invalid UTF-8 at position {pos}"),
+            Self::InvalidOptionTag(tag) => write!(f, "invalid `Option` tag {tag}"),
+            Self::DuplicateElement(pos) => write!(f, "duplicate element at position {pos}"),
+            Self::InvalidDiscriminant(discriminant) => {
+                write!(f, "invalid enum discriminant {discriminant}")
+            }
+            Self::InvalidVarInt(pos) => {
+                write!(f, "invalid variable-length integer at position {pos}")
+            }
+            Self::Io(err) => write!(f, "I/O error: {err}"),
+            Self::BorrowUnsupported(pos) => {
+                write!(f, "cannot borrow from a streaming reader at position {pos}")
+            }
+            Self::RewindUnsupported(pos) => {
+                write!(f, "cannot rewind a streaming reader at position {pos}")
+            }
+            Self::NonCanonicalNan(pos) => write!(f, "non-canonical NaN at position {pos}"),
+            Self::InvalidTime(pos) => write!(f, "invalid time at position {pos}"),
+            Self::UnsupportedVersion(version) => write!(f, "unsupported version {version}"),
+            Self::InvalidMagic(magic) => write!(f, "invalid magic number {magic:#010x}"),
+            Self::MissingField => write!(f, "missing field"),
+            Self::DuplicateField(pos) => write!(f, "duplicate field at position {pos}"),
+            Self::FieldLengthMismatch {
+                pos,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "field length mismatch at position {pos}: expected {expected} bytes, read {actual}"
+            ),
+            Self::LimitExceeded(pos) => write!(f, "limit exceeded at position {pos}"),
+            Self::InvalidHex(pos) => write!(f, "invalid hex at position {pos}"),
+            Self::InvalidBase64(pos) => write!(f, "invalid Base64 at position {pos}"),
+            Self::NonZeroPadding(pos) => write!(f, "non-zero padding bits at position {pos}"),
+            Self::InvalidCompression(pos) => write!(f, "invalid compressed data at position {pos}"),
+            Self::InvalidSeal => write!(f, "sealed data failed authentication"),
+            Self::InvalidWireType(pos) => write!(f, "invalid wire type at position {pos}"),
+            Self::DepthExceeded(pos) => write!(f, "nesting depth exceeded at position {pos}"),
+            Self::ValueTooWide { index, bits } => {
+                write!(f, "value at index {index} does not fit in {bits} bits")
+            }
+            Self::Invalid { pos, source } => write!(f, "invalid value at position {pos}: {source}"),
+            Self::InField { ty, field, source } => write!(f, "{ty}.{field}: {source}"),
         }
     }
 }
 
-pub struct BytesWriter {
-    data: Box<[MaybeUninit<u8>]>,
+pub struct BytesWriter<'a> {
+    backend: WriterBackend<'a>,
     written: usize,
 }
 
-impl BytesWriter {
-    fn new(capacity: usize) -> Self {
-        Self {
-            data: Box::new_uninit_slice(capacity),
-            written: 0,
-        }
+enum WriterBackend<'a> {
+    #[cfg(not(feature = "forbid-unsafe"))]
+    Buffer(Box<[MaybeUninit<u8>]>),
+    #[cfg(feature = "forbid-unsafe")]
+    Buffer(Box<[u8]>),
+    Vec(&'a mut Vec<u8>),
+    Stream {
+        inner: &'a mut dyn io::Write,
+        staging: Vec<u8>,
+        error: Option<io::Error>,
+    },
+}
+
+// Small writes to a stream are collected in a staging buffer of this size, so that encoding many
+// small fields doesn't turn into as many calls to the underlying writer:
+const STAGING_CAPACITY: usize = 8 * 1024;
+const STAGING_ELEM_MAX: usize = 64;
+
+impl<'a> BytesWriter<'a> {
+    pub fn new(capacity: usize) -> Self {
+        #[cfg(not(feature = "forbid-unsafe"))]
+        let data = Box::new_uninit_slice(capacity);
+        #[cfg(feature = "forbid-unsafe")]
+        let data = vec![0; capacity].into_boxed_slice();
+
+        Self {
+            backend: WriterBackend::Buffer(data),
+            written: 0,
+        }
+    }
+
+    pub fn appending(buf: &'a mut Vec<u8>) -> Self {
+        Self {
+            backend: WriterBackend::Vec(buf),
+            written: 0,
+        }
+    }
+
+    pub fn streaming(inner: &'a mut dyn io::Write) -> Self {
+        Self {
+            backend: WriterBackend::Stream {
+                inner,
+                staging: Vec::with_capacity(STAGING_CAPACITY),
+                error: None,
+            },
+            written: 0,
+        }
+    }
+
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    pub fn capacity(&self) -> Option<usize> {
+        match &self.backend {
+            WriterBackend::Buffer(data) => Some(data.len()),
+            WriterBackend::Vec(_) | WriterBackend::Stream { .. } => None,
+        }
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        match &mut self.backend {
+            WriterBackend::Buffer(data) => {
+                let Some(data) = data.get_mut(self.written..self.written + bytes.len()) else {
+                    panic!("writer capacity exceeded");
+                };
+
+                #[cfg(not(feature = "forbid-unsafe"))]
+                data.write_copy_of_slice(bytes);
+                #[cfg(feature = "forbid-unsafe")]
+                data.copy_from_slice(bytes);
+            }
+            WriterBackend::Vec(buf) => buf.extend_from_slice(bytes),
+            WriterBackend::Stream { staging, .. }
+                if staging.len() + bytes.len() <= STAGING_CAPACITY =>
+            {
+                staging.extend_from_slice(bytes);
+            }
+            WriterBackend::Stream { .. } => self.write_stream(bytes),
+        }
+
+        self.written += bytes.len();
+    }
+
+    #[cold]
+    fn write_stream(&mut self, bytes: &[u8]) {
+        let WriterBackend::Stream {
+            inner,
+            staging,
+            error,
+        } = &mut self.backend
+        else {
+            unreachable!();
+        };
+
+        // `Bytes::write` is infallible, so the first I/O error is kept until the writer is
+        // finished, and any subsequent writes are skipped:
+        if error.is_none()
+            && let Err(err) = inner.write_all(staging)
+        {
+            *error = Some(err);
+        }
+        staging.clear();
+
+        if bytes.len() < STAGING_CAPACITY {
+            staging.extend_from_slice(bytes);
+        } else if error.is_none()
+            && let Err(err) = inner.write_all(bytes)
+        {
+            *error = Some(err);
+        }
+    }
+
+    pub fn finish(self) -> Box<[u8]> {
+        let WriterBackend::Buffer(data) = self.backend else {
+            panic!("writer is not buffered");
+        };
+
+        assert_eq!(self.written, data.len());
+
+        // SAFETY: Since `self.written` is equal to the data length, then the data has been fully
+        // initialized:
+        #[cfg(not(feature = "forbid-unsafe"))]
+        let data = unsafe { data.assume_init() };
+
+        data
+    }
+
+    pub fn finish_streaming(self) -> io::Result<()> {
+        let WriterBackend::Stream {
+            inner,
+            staging,
+            error,
+        } = self.backend
+        else {
+            panic!("writer is not streaming");
+        };
+
+        error.map_or_else(|| inner.write_all(&staging), Err)
+    }
+}
+
+pub struct BytesReader<'a> {
+    backend: ReaderBackend<'a>,
+    read: usize,
+    limits: ReadLimits,
+    allocated: usize,
+    depth: usize,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ReadLimits {
+    pub max_len: usize,
+    pub max_alloc: usize,
+    pub max_depth: usize,
+}
+
+// Unlike the other limits, the depth is bounded by default, since exceeding it overflows the stack,
+// while collections never preallocate more than the input could hold. No reasonable type nests
+// this deeply:
+const DEFAULT_MAX_DEPTH: usize = 128;
+
+impl Default for ReadLimits {
+    fn default() -> Self {
+        Self {
+            max_len: usize::MAX,
+            max_alloc: usize::MAX,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+impl ReadLimits {
+    // Caps allocations relative to the size of the input, so that a corrupted length prefix can't
+    // make the decoder allocate much more memory than it was given:
+    pub fn proportional(len: usize) -> Self {
+        Self {
+            max_alloc: (1 << 20) + len.saturating_mul(64),
+            ..Default::default()
+        }
+    }
+}
+
+// Streams don't know how much data is left, so at most this many bytes are preallocated for a
+// collection read from one:
+const STREAM_PREALLOC_MAX: usize = 64 * 1024;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Checkpoint {
+    read: usize,
+    allocated: usize,
+}
+
+enum ReaderBackend<'a> {
+    Slice(&'a [u8]),
+    Stream(&'a mut dyn io::Read),
+}
+
+impl<'a> BytesReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self::with_limits(bytes, ReadLimits::default())
+    }
+
+    pub fn with_limits(bytes: &'a [u8], limits: ReadLimits) -> Self {
+        Self {
+            backend: ReaderBackend::Slice(bytes),
+            read: 0,
+            limits,
+            allocated: 0,
+            depth: 0,
+        }
+    }
+
+    pub fn streaming(inner: &'a mut dyn io::Read, limits: ReadLimits) -> Self {
+        Self {
+            backend: ReaderBackend::Stream(inner),
+            read: 0,
+            limits,
+            allocated: 0,
+            depth: 0,
+        }
+    }
+
+    pub fn position(&self) -> usize {
+        self.read
+    }
+
+    pub fn remaining(&self) -> Option<usize> {
+        match &self.backend {
+            ReaderBackend::Slice(data) => Some(data.len() - self.read),
+            ReaderBackend::Stream(_) => None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.remaining() == Some(0)
+    }
+
+    // Reads a record of `len` bytes with a nested reader that ends where the record ends, and fails
+    // unless `read` consumes all of it.
+    pub fn sub_reader<T, F>(&mut self, len: usize, read: F) -> Result<T, BytesError>
+    where
+        F: FnOnce(&mut BytesReader<'a>) -> Result<T, BytesError>,
+    {
+        let ReaderBackend::Slice(data) = self.backend else {
+            return Err(BytesError::BorrowUnsupported(self.read));
+        };
+
+        if len > data.len() - self.read {
+            return Err(self.end_of_data::<[u8]>(len));
+        }
+
+        // The nested reader shares the same underlying data, but ends where the record ends, so
+        // that positions in errors are still relative to the start of the data:
+        let end = self.read + len;
+        let mut sub_reader = Self {
+            backend: ReaderBackend::Slice(&data[..end]),
+            read: self.read,
+            limits: self.limits,
+            allocated: self.allocated,
+            depth: self.depth,
+        };
+
+        let output = read(&mut sub_reader)?;
+
+        // Allocations made while reading the record count towards the limits of the whole data,
+        // so that a payload can't get around them by being split into records:
+        self.allocated = sub_reader.allocated;
+        sub_reader.finish()?;
+        self.read = end;
+
+        Ok(output)
+    }
+
+    pub fn checkpoint(&self) -> Result<Checkpoint, BytesError> {
+        match self.backend {
+            ReaderBackend::Slice(_) => Ok(Checkpoint {
+                read: self.read,
+                allocated: self.allocated,
+            }),
+            ReaderBackend::Stream(_) => Err(BytesError::RewindUnsupported(self.read)),
+        }
+    }
+
+    pub fn restore(&mut self, checkpoint: Checkpoint) {
+        let ReaderBackend::Slice(data) = self.backend else {
+            panic!("reader is not rewindable");
+        };
+
+        assert!(checkpoint.read <= data.len());
+
+        self.read = checkpoint.read;
+        self.allocated = checkpoint.allocated;
+    }
+
+    // Reads a nested value, failing instead of recursing deeper than the limit allows. The derive
+    // template wraps every `read` in it, so that recursive types can't overflow the stack.
+    pub fn nested<T, F>(&mut self, read: F) -> Result<T, BytesError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, BytesError>,
+    {
+        if self.depth >= self.limits.max_depth {
+            return Err(BytesError::DepthExceeded(self.read));
+        }
+
+        self.depth += 1;
+        let output = read(self);
+        self.depth -= 1;
+        output
+    }
+
+    pub fn peek<T>(&mut self) -> Result<T, BytesError>
+    where
+        T: Bytes,
+    {
+        let checkpoint = self.checkpoint()?;
+        let output = T::read(self);
+        self.restore(checkpoint);
+        output
+    }
+
+    // The data ended while reading `needed` bytes of a `T`:
+    fn end_of_data<T>(&self, needed: usize) -> BytesError
+    where
+        T: ?Sized,
+    {
+        BytesError::EndOfData {
+            pos: self.read,
+            needed,
+            available: self.remaining(),
+            ty: std::any::type_name::<T>(),
+        }
+    }
+
+    fn read<T>(&mut self, bytes: &mut [u8]) -> Result<(), BytesError>
+    where
+        T: ?Sized,
+    {
+        match &mut self.backend {
+            ReaderBackend::Slice(data) => {
+                if self.read + bytes.len() > data.len() {
+                    return Err(self.end_of_data::<T>(bytes.len()));
+                }
+
+                bytes.copy_from_slice(&data[self.read..self.read + bytes.len()]);
+            }
+            ReaderBackend::Stream(inner) => match inner.read_exact(bytes) {
+                Ok(()) => {}
+                Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => {
+                    return Err(self.end_of_data::<T>(bytes.len()));
+                }
+                Err(err) => return Err(BytesError::Io(err)),
+            },
+        }
+
+        self.read += bytes.len();
+
+        Ok(())
+    }
+
+    fn read_vec<T>(&mut self, len: usize) -> Result<Vec<u8>, BytesError>
+    where
+        T: ?Sized,
+    {
+        let ReaderBackend::Stream(inner) = &mut self.backend else {
+            return self.read_borrowed::<T>(len).map(<[u8]>::to_vec);
+        };
+
+        // The length may come from an untrusted prefix, so the buffer only grows with the data
+        // that actually arrives, instead of being allocated up front:
+        let mut bytes = Vec::with_capacity(len.min(STREAM_PREALLOC_MAX));
+        let mut inner = io::Read::take(&mut **inner, len as u64);
+        io::Read::read_to_end(&mut inner, &mut bytes).map_err(BytesError::Io)?;
+        if bytes.len() < len {
+            return Err(BytesError::EndOfData {
+                pos: self.read,
+                needed: len,
+                available: Some(bytes.len()),
+                ty: std::any::type_name::<T>(),
+            });
+        }
+
+        self.read += len;
+
+        Ok(bytes)
+    }
+
+    fn read_len<T>(&mut self) -> Result<usize, BytesError> {
+        let pos = self.read;
+        let len = u64::read(self)?
+            .try_into()
+            .map_err(|_| BytesError::UsizeTooSmall)?;
+        self.reserve::<T>(pos, len)?;
+        Ok(len)
+    }
+
+    fn read_elems_len<T>(&mut self) -> Result<usize, BytesError>
+    where
+        T: Bytes,
+    {
+        let len = self.read_len::<T>()?;
+        self.check_fixed_len::<T>(len)?;
+        Ok(len)
+    }
+
+    // Fails early if `len` elements of a fixed-size type can't fit in the remaining data, which
+    // avoids allocating for and decoding a prefix of a truncated collection:
+    fn check_fixed_len<T>(&self, len: usize) -> Result<(), BytesError>
+    where
+        T: Bytes,
+    {
+        if let (Some(size), Some(remaining)) = (T::FIXED_SIZE, self.remaining())
+            && len.saturating_mul(size) > remaining
+        {
+            return Err(self.end_of_data::<T>(len.saturating_mul(size)));
+        }
+
+        Ok(())
+    }
+
+    // Bounds the capacity preallocated for `len` elements of type `T` by how many of them the
+    // remaining data could hold. The limits only reject lengths once they are exceeded, which is
+    // too late if the default limits are unbounded, so this keeps an untrusted length prefix from
+    // allocating more than the input justifies. Collections still grow if the elements are there.
+    fn capacity_for<T>(&self, len: usize) -> usize
+    where
+        T: Bytes,
+    {
+        let max = match self.remaining() {
+            Some(remaining) => remaining / T::FIXED_SIZE.unwrap_or(1).max(1),
+            None => STREAM_PREALLOC_MAX / size_of::<T>().max(1),
+        };
+        len.min(max)
+    }
+
+    // Accounts for the allocation of `len` elements of type `T`, which must be done before
+    // allocating, as the length prefix may come from an untrusted source:
+    fn reserve<T>(&mut self, pos: usize, len: usize) -> Result<(), BytesError> {
+        self.allocated = self
+            .allocated
+            .saturating_add(len.saturating_mul(size_of::<T>()));
+        if len > self.limits.max_len || self.allocated > self.limits.max_alloc {
+            return Err(BytesError::LimitExceeded(pos));
+        }
+
+        Ok(())
+    }
+
+    fn read_borrowed<T>(&mut self, len: usize) -> Result<&'a [u8], BytesError>
+    where
+        T: ?Sized,
+    {
+        let ReaderBackend::Slice(data) = self.backend else {
+            return Err(BytesError::BorrowUnsupported(self.read));
+        };
+
+        // The length may come from an untrusted prefix, so it's compared against the remaining
+        // data instead of being added to the position, which could overflow:
+        if len > data.len() - self.read {
+            return Err(self.end_of_data::<T>(len));
+        }
+
+        let bytes = &data[self.read..self.read + len];
+        self.read += len;
+
+        Ok(bytes)
+    }
+
+    // Skips `len` bytes without buffering them, such as a field unknown to the reader.
+    fn skip<T>(&mut self, len: usize) -> Result<(), BytesError>
+    where
+        T: ?Sized,
+    {
+        let ReaderBackend::Stream(inner) = &mut self.backend else {
+            return self.read_borrowed::<T>(len).map(drop);
+        };
+
+        let mut inner = io::Read::take(&mut **inner, len as u64);
+        let skipped = io::copy(&mut inner, &mut io::sink()).map_err(BytesError::Io)?;
+        if skipped < len as u64 {
+            return Err(BytesError::EndOfData {
+                pos: self.read,
+                needed: len,
+                available: Some(skipped as usize),
+                ty: std::any::type_name::<T>(),
+            });
+        }
+
+        self.read += len;
+
+        Ok(())
+    }
+
+    pub fn finish(self) -> Result<(), BytesError> {
+        let at_end = match self.backend {
+            ReaderBackend::Slice(data) => self.read == data.len(),
+            ReaderBackend::Stream(inner) => inner.read(&mut [0]).map_err(BytesError::Io)? == 0,
+        };
+
+        if at_end {
+            Ok(())
+        } else {
+            Err(BytesError::TrailingData(self.read))
+        }
+    }
+}
+
+fn elems_size<'a, T>(elems: impl ExactSizeIterator<Item = &'a T>) -> usize
+where
+    T: Bytes + 'a,
+{
+    match T::FIXED_SIZE {
+        Some(size) => elems.len() * size,
+        None => elems.map(|elem| elem.required_size()).sum(),
+    }
+}
+
+fn write_elems<T>(elems: &[T], writer: &mut BytesWriter)
+where
+    T: Bytes,
+{
+    if let Some(bytes) = T::as_byte_slice(elems) {
+        writer.write(bytes);
+        return;
+    }
+
+    // Small fixed-size elements are encoded in chunks into a staging buffer, which is then written
+    // in one call, instead of going through the writer's backend for every field:
+    if let Some(size @ 1..=STAGING_ELEM_MAX) = T::FIXED_SIZE {
+        let mut staging = Vec::with_capacity(STAGING_CAPACITY);
+        for chunk in elems.chunks(STAGING_CAPACITY / size) {
+            staging.clear();
+            let mut staging_writer = BytesWriter::appending(&mut staging);
+            for elem in chunk {
+                elem.write(&mut staging_writer);
+            }
+            writer.write(&staging);
+        }
+        return;
+    }
+
+    for elem in elems {
+        elem.write(writer);
+    }
+}
+
+fn read_elems<T>(reader: &mut BytesReader, len: usize) -> Result<Vec<T>, BytesError>
+where
+    T: Bytes,
+{
+    if let Some(output) = T::read_byte_vec(reader, len) {
+        return output;
+    }
+
+    let mut elems = Vec::with_capacity(reader.capacity_for::<T>(len));
+    for _ in 0..len {
+        elems.push(T::read(reader)?);
+    }
+
+    Ok(elems)
+}
+
+#[cfg(not(feature = "forbid-unsafe"))]
+fn read_array<const N: usize, T>(reader: &mut BytesReader) -> Result<[T; N], BytesError>
+where
+    T: Bytes,
+{
+    let mut elems = [const { MaybeUninit::<T>::uninit() }; N];
+    for elem in &mut elems {
+        elem.write(T::read(reader)?);
+    }
+
+    // SAFETY: `elems` is fully initialized by the reader.
+    Ok(unsafe { mem::transmute_copy(&elems) })
+}
+
+#[cfg(feature = "forbid-unsafe")]
+fn read_array<const N: usize, T>(reader: &mut BytesReader) -> Result<[T; N], BytesError>
+where
+    T: Bytes,
+{
+    let elems = read_elems(reader, N)?;
+    Ok(elems.try_into().unwrap_or_else(|_| unreachable!()))
+}
+
+macro_rules! impl_bytes_for_int {
+    ($ty:ty) => {
+        impl_bytes_for_int!($ty {});
+    };
+    ($ty:ty { $($hooks:item)* }) => {
+        impl Bytes for $ty {
+            const FIXED_SIZE: Option<usize> = Some(size_of::<$ty>());
+
+            #[inline]
+            fn required_size(&self) -> usize {
+                size_of::<$ty>()
+            }
+
+            #[inline]
+            fn write(&self, writer: &mut BytesWriter) {
+                writer.write(&self.to_le_bytes());
+            }
+
+            #[inline]
+            fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+                let mut bytes = [0; size_of::<$ty>()];
+                reader.read::<$ty>(&mut bytes)?;
+                Ok(<$ty>::from_le_bytes(bytes))
+            }
+
+            $($hooks)*
+        }
+    };
+}
+
+impl_bytes_for_int!(u8 {
+    fn as_byte_slice(elems: &[Self]) -> Option<&[u8]> {
+        Some(elems)
+    }
+
+    fn read_byte_vec(reader: &mut BytesReader, len: usize) -> Option<Result<Vec<Self>, BytesError>> {
+        Some(reader.read_vec::<[u8]>(len))
+    }
+
+    fn read_byte_array<const N: usize>(
+        reader: &mut BytesReader,
+    ) -> Option<Result<[Self; N], BytesError>> {
+        let mut bytes = [0; N];
+        Some(reader.read::<[u8; N]>(&mut bytes).map(|()| bytes))
+    }
+});
+impl_bytes_for_int!(u16);
+impl_bytes_for_int!(u32);
+impl_bytes_for_int!(u64);
+impl_bytes_for_int!(u128);
+impl_bytes_for_int!(i8);
+impl_bytes_for_int!(i16);
+impl_bytes_for_int!(i32);
+impl_bytes_for_int!(i64);
+impl_bytes_for_int!(i128);
+
+impl Bytes for usize {
+    const FIXED_SIZE: Option<usize> = u64::FIXED_SIZE;
+
+    #[inline]
+    fn required_size(&self) -> usize {
+        u64::required_size(&(*self as u64))
+    }
+
+    #[inline]
+    fn write(&self, writer: &mut BytesWriter) {
+        u64::write(&(*self as u64), writer);
+    }
+
+    #[inline]
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        u64::read(reader)?
+            .try_into()
+            .map_err(|_| BytesError::UsizeTooSmall)
+    }
+}
+
+impl Bytes for isize {
+    const FIXED_SIZE: Option<usize> = i64::FIXED_SIZE;
+
+    #[inline]
+    fn required_size(&self) -> usize {
+        i64::required_size(&(*self as i64))
+    }
+
+    #[inline]
+    fn write(&self, writer: &mut BytesWriter) {
+        i64::write(&(*self as i64), writer);
+    }
+
+    #[inline]
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        i64::read(reader)?
+            .try_into()
+            .map_err(|_| BytesError::UsizeTooSmall)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct CanonicalNan<T>(pub T);
+
+macro_rules! impl_bytes_for_float {
+    ($ty:ty, $bits:ty) => {
+        impl Bytes for $ty {
+            const FIXED_SIZE: Option<usize> = <$bits>::FIXED_SIZE;
+
+            fn required_size(&self) -> usize {
+                size_of::<$bits>()
+            }
+
+            fn write(&self, writer: &mut BytesWriter) {
+                self.to_bits().write(writer);
+            }
+
+            fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+                <$bits>::read(reader).map(<$ty>::from_bits)
+            }
+        }
+
+        impl Bytes for CanonicalNan<$ty> {
+            const FIXED_SIZE: Option<usize> = <$bits>::FIXED_SIZE;
+
+            fn required_size(&self) -> usize {
+                size_of::<$bits>()
+            }
+
+            fn write(&self, writer: &mut BytesWriter) {
+                if self.0.is_nan() {
+                    <$ty>::NAN.write(writer);
+                } else {
+                    self.0.write(writer);
+                }
+            }
+
+            fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+                let pos = reader.read;
+                let value = <$ty>::read(reader)?;
+                if value.is_nan() && value.to_bits() != <$ty>::NAN.to_bits() {
+                    return Err(BytesError::NonCanonicalNan(pos));
+                }
+
+                Ok(Self(value))
+            }
+        }
+    };
+}
+
+impl_bytes_for_float!(f32, u32);
+impl_bytes_for_float!(f64, u64);
+
+impl Bytes for Duration {
+    const FIXED_SIZE: Option<usize> = Some(12);
+
+    fn required_size(&self) -> usize {
+        size_of::<u64>() + size_of::<u32>()
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        self.as_secs().write(writer);
+        self.subsec_nanos().write(writer);
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        let pos = reader.read;
+        let secs = u64::read(reader)?;
+        let nanos = u32::read(reader)?;
+        if nanos >= 1_000_000_000 {
+            return Err(BytesError::InvalidTime(pos));
+        }
+
+        Ok(Duration::new(secs, nanos))
+    }
+}
+
+// A `SystemTime` is encoded as the signed number of whole seconds relative to the Unix epoch,
+// followed by the non-negative number of nanoseconds past that second.
+impl Bytes for SystemTime {
+    const FIXED_SIZE: Option<usize> = Some(12);
+
+    fn required_size(&self) -> usize {
+        size_of::<i64>() + size_of::<u32>()
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        let (secs, nanos) = match self.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(after) => (after.as_secs() as i128, after.subsec_nanos()),
+            Err(err) => {
+                let before = err.duration();
+                match before.subsec_nanos() {
+                    0 => (-(before.as_secs() as i128), 0),
+                    nanos => (-(before.as_secs() as i128) - 1, 1_000_000_000 - nanos),
+                }
+            }
+        };
+
+        i64::try_from(secs)
+            .expect("time is out of range")
+            .write(writer);
+        nanos.write(writer);
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        let pos = reader.read;
+        let secs = i64::read(reader)?;
+        let nanos = u32::read(reader)?;
+        if nanos >= 1_000_000_000 {
+            return Err(BytesError::InvalidTime(pos));
+        }
+
+        let time = if secs >= 0 {
+            SystemTime::UNIX_EPOCH.checked_add(Duration::from_secs(secs as u64))
+        } else {
+            SystemTime::UNIX_EPOCH.checked_sub(Duration::from_secs(secs.unsigned_abs()))
+        };
+
+        time.and_then(|time| time.checked_add(Duration::from_nanos(nanos as u64)))
+            .ok_or(BytesError::InvalidTime(pos))
+    }
+}
+
+impl Bytes for bool {
+    const FIXED_SIZE: Option<usize> = u8::FIXED_SIZE;
+
+    fn required_size(&self) -> usize {
+        u8::required_size(&(*self as u8))
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        u8::write(&(*self as u8), writer);
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        match u8::read(reader)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(BytesError::InvalidBool(value)),
+        }
+    }
+}
+
+impl Bytes for char {
+    const FIXED_SIZE: Option<usize> = u32::FIXED_SIZE;
+
+    fn required_size(&self) -> usize {
+        u32::required_size(&(*self as u32))
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        u32::write(&(*self as u32), writer);
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        let value = u32::read(reader)?;
+        char::from_u32(value).ok_or(BytesError::InvalidChar(value))
+    }
+}
+
+impl Bytes for Ipv4Addr {
+    const FIXED_SIZE: Option<usize> = <[u8; 4]>::FIXED_SIZE;
+
+    fn required_size(&self) -> usize {
+        self.octets().required_size()
     }
 
-    fn write(&mut self, bytes: &[u8]) {
-        assert!(self.written + bytes.len() <= self.data.len());
+    fn write(&self, writer: &mut BytesWriter) {
+        self.octets().write(writer);
+    }
 
-        // SAFETY: `MaybeUninit<u8>` has the same size and layout as `u8`.
-        self.data[self.written..self.written + bytes.len()]
-            .copy_from_slice(unsafe { mem::transmute(bytes) });
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        <[u8; 4]>::read(reader).map(Ipv4Addr::from)
+    }
+}
 
-        self.written += bytes.len();
+impl Bytes for Ipv6Addr {
+    const FIXED_SIZE: Option<usize> = <[u8; 16]>::FIXED_SIZE;
+
+    fn required_size(&self) -> usize {
+        self.octets().required_size()
     }
 
-    fn finish(self) -> Box<[u8]> {
-        assert_eq!(self.written, self.data.len());
+    fn write(&self, writer: &mut BytesWriter) {
+        self.octets().write(writer);
+    }
 
-        // SAFETY: Since `self.written` is equal to the data length, then the data has been fully
-        // initialized:
-        unsafe { self.data.assume_init() }
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        <[u8; 16]>::read(reader).map(Ipv6Addr::from)
     }
 }
 
-pub struct BytesReader<'a> {
-    data: &'a [u8],
-    read: usize,
-}
+impl Bytes for IpAddr {
+    fn required_size(&self) -> usize {
+        1 + match self {
+            IpAddr::V4(addr) => addr.required_size(),
+            IpAddr::V6(addr) => addr.required_size(),
+        }
+    }
 
-impl<'a> BytesReader<'a> {
-    fn new(bytes: &'a [u8]) -> Self {
-        Self {
-            data: bytes,
-            read: 0,
+    fn write(&self, writer: &mut BytesWriter) {
+        match self {
+            IpAddr::V4(addr) => {
+                0u8.write(writer);
+                addr.write(writer);
+            }
+            IpAddr::V6(addr) => {
+                1u8.write(writer);
+                addr.write(writer);
+            }
         }
     }
 
-    fn read(&mut self, bytes: &mut [MaybeUninit<u8>]) -> Result<(), BytesError> {
-        if self.read + bytes.len() > self.data.len() {
-            return Err(BytesError::EndOfData(self.read));
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        match u8::read(reader)? {
+            0 => Ipv4Addr::read(reader).map(IpAddr::V4),
+            1 => Ipv6Addr::read(reader).map(IpAddr::V6),
+            tag => Err(BytesError::InvalidDiscriminant(tag as u64)),
         }
+    }
+}
 
-        // SAFETY: `MaybeUninit<u8>` has the same size and layout as `u8`.
-        bytes.copy_from_slice(unsafe {
-            mem::transmute(&self.data[self.read..self.read + bytes.len()])
-        });
+impl Bytes for SocketAddrV4 {
+    const FIXED_SIZE: Option<usize> = fixed::sum(&[Ipv4Addr::FIXED_SIZE, u16::FIXED_SIZE]);
 
-        self.read += bytes.len();
+    fn required_size(&self) -> usize {
+        self.ip().required_size() + self.port().required_size()
+    }
 
-        Ok(())
+    fn write(&self, writer: &mut BytesWriter) {
+        self.ip().write(writer);
+        self.port().write(writer);
     }
 
-    fn finish(self) -> Result<(), BytesError> {
-        if self.read == self.data.len() {
-            Ok(())
-        } else {
-            Err(BytesError::TrailingData(self.read))
-        }
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        let ip = Ipv4Addr::read(reader)?;
+        let port = u16::read(reader)?;
+        Ok(SocketAddrV4::new(ip, port))
     }
 }
 
-macro_rules! impl_bytes_for_uint {
-    ($ty:ty) => {
-        impl Bytes for $ty {
-            fn required_size(&self) -> usize {
-                size_of::<$ty>()
-            }
+// The flow info and scope ID are included, so that link-local addresses survive a round trip.
+impl Bytes for SocketAddrV6 {
+    const FIXED_SIZE: Option<usize> = fixed::sum(&[
+        Ipv6Addr::FIXED_SIZE,
+        u16::FIXED_SIZE,
+        u32::FIXED_SIZE,
+        u32::FIXED_SIZE,
+    ]);
 
-            fn write(&self, writer: &mut BytesWriter) {
-                writer.write(&self.to_le_bytes());
-            }
+    fn required_size(&self) -> usize {
+        self.ip().required_size()
+            + self.port().required_size()
+            + self.flowinfo().required_size()
+            + self.scope_id().required_size()
+    }
 
-            fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
-                let mut bytes = [MaybeUninit::uninit(); size_of::<$ty>()];
-                reader.read(&mut bytes)?;
+    fn write(&self, writer: &mut BytesWriter) {
+        self.ip().write(writer);
+        self.port().write(writer);
+        self.flowinfo().write(writer);
+        self.scope_id().write(writer);
+    }
 
-                // SAFETY: `bytes` is fully initialized by the reader.
-                Ok(<$ty>::from_le_bytes(unsafe { mem::transmute_copy(&bytes) }))
-            }
-        }
-    };
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        let ip = Ipv6Addr::read(reader)?;
+        let port = u16::read(reader)?;
+        let flowinfo = u32::read(reader)?;
+        let scope_id = u32::read(reader)?;
+        Ok(SocketAddrV6::new(ip, port, flowinfo, scope_id))
+    }
 }
 
-impl_bytes_for_uint!(u8);
-impl_bytes_for_uint!(u64);
-
-impl Bytes for usize {
+impl Bytes for SocketAddr {
     fn required_size(&self) -> usize {
-        u64::required_size(&(*self as u64))
+        1 + match self {
+            SocketAddr::V4(addr) => addr.required_size(),
+            SocketAddr::V6(addr) => addr.required_size(),
+        }
     }
 
     fn write(&self, writer: &mut BytesWriter) {
-        u64::write(&(*self as u64), writer);
+        match self {
+            SocketAddr::V4(addr) => {
+                0u8.write(writer);
+                addr.write(writer);
+            }
+            SocketAddr::V6(addr) => {
+                1u8.write(writer);
+                addr.write(writer);
+            }
+        }
     }
 
     fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
-        u64::read(reader)?
-            .try_into()
-            .map_err(|_| BytesError::UsizeTooSmall)
+        match u8::read(reader)? {
+            0 => SocketAddrV4::read(reader).map(SocketAddr::V4),
+            1 => SocketAddrV6::read(reader).map(SocketAddr::V6),
+            tag => Err(BytesError::InvalidDiscriminant(tag as u64)),
+        }
     }
 }
 
@@ -169,6 +1349,8 @@ macro_rules! impl_bytes_for_tuple {
         where
             $($t: Bytes,)+
         {
+            const FIXED_SIZE: Option<usize> = fixed::sum(&[$($t::FIXED_SIZE),+]);
+
             fn required_size(&self) -> usize {
                 $(self.$i.required_size() +)+ 0
             }
@@ -263,24 +1445,19 @@ impl<const N: usize, T> Bytes for [T; N]
 where
     T: Bytes,
 {
+    const FIXED_SIZE: Option<usize> = fixed::product(T::FIXED_SIZE, N);
+
     fn required_size(&self) -> usize {
-        self.iter().map(|elem| elem.required_size()).sum()
+        elems_size(self.iter())
     }
 
     fn write(&self, writer: &mut BytesWriter) {
-        for elem in self {
-            elem.write(writer);
-        }
+        write_elems(self, writer);
     }
 
     fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
-        let mut elems = [const { MaybeUninit::uninit() }; N];
-        for i in 0..N {
-            elems[i].write(T::read(reader)?);
-        }
-
-        // SAFETY: `elems` is fully initialized by the reader.
-        Ok(unsafe { mem::transmute_copy(&elems) })
+        reader.check_fixed_len::<T>(N)?;
+        T::read_byte_array(reader).unwrap_or_else(|| read_array(reader))
     }
 }
 
@@ -289,7 +1466,49 @@ where
     T: Bytes,
 {
     fn required_size(&self) -> usize {
-        8 + self.iter().map(|elem| elem.required_size()).sum::<usize>()
+        8 + elems_size(self.iter())
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        let len = self.len();
+        (len as u64).write(writer);
+
+        write_elems(self, writer);
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        Vec::read(reader).map(Vec::into_boxed_slice)
+    }
+}
+
+impl<T> Bytes for Vec<T>
+where
+    T: Bytes,
+{
+    fn required_size(&self) -> usize {
+        8 + elems_size(self.iter())
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        let len = self.len();
+        (len as u64).write(writer);
+
+        write_elems(self, writer);
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        let len = reader.read_elems_len::<T>()?;
+        read_elems(reader, len)
+    }
+}
+
+impl<T, S> Bytes for HashSet<T, S>
+where
+    T: Bytes + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn required_size(&self) -> usize {
+        8 + elems_size(self.iter())
     }
 
     fn write(&self, writer: &mut BytesWriter) {
@@ -302,25 +1521,26 @@ where
     }
 
     fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
-        let len = u64::read(reader)?
-            .try_into()
-            .map_err(|_| BytesError::UsizeTooSmall)?;
-        let mut elems = Box::new_uninit_slice(len);
-        for i in 0..len {
-            elems[i].write(T::read(reader)?);
+        let len = reader.read_elems_len::<T>()?;
+        let mut elems =
+            HashSet::with_capacity_and_hasher(reader.capacity_for::<T>(len), S::default());
+        for _ in 0..len {
+            let pos = reader.read;
+            if !elems.insert(T::read(reader)?) {
+                return Err(BytesError::DuplicateElement(pos));
+            }
         }
 
-        // SAFETY: `elems` is fully initialized by the reader.
-        Ok(unsafe { elems.assume_init() })
+        Ok(elems)
     }
 }
 
-impl<T> Bytes for Vec<T>
+impl<T> Bytes for BTreeSet<T>
 where
-    T: Bytes,
+    T: Bytes + Ord,
 {
     fn required_size(&self) -> usize {
-        8 + self.iter().map(|elem| elem.required_size()).sum::<usize>()
+        8 + elems_size(self.iter())
     }
 
     fn write(&self, writer: &mut BytesWriter) {
@@ -333,43 +1553,527 @@ where
     }
 
     fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
-        let len = u64::read(reader)?
-            .try_into()
-            .map_err(|_| BytesError::UsizeTooSmall)?;
-        let mut elems = Vec::with_capacity(len);
+        let len = reader.read_elems_len::<T>()?;
+        let mut elems = BTreeSet::new();
         for _ in 0..len {
-            elems.push(T::read(reader)?);
+            let pos = reader.read;
+            if !elems.insert(T::read(reader)?) {
+                return Err(BytesError::DuplicateElement(pos));
+            }
         }
 
         Ok(elems)
     }
 }
 
+macro_rules! impl_bytes_for_pointer {
+    ($ptr:ident) => {
+        impl<T> Bytes for $ptr<T>
+        where
+            T: Bytes,
+        {
+            const FIXED_SIZE: Option<usize> = T::FIXED_SIZE;
+
+            fn required_size(&self) -> usize {
+                T::required_size(self)
+            }
+
+            fn write(&self, writer: &mut BytesWriter) {
+                T::write(self, writer);
+            }
+
+            fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+                T::read(reader).map($ptr::new)
+            }
+        }
+    };
+}
+
+impl_bytes_for_pointer!(Box);
+impl_bytes_for_pointer!(Rc);
+impl_bytes_for_pointer!(Arc);
+
+impl Bytes for () {
+    const FIXED_SIZE: Option<usize> = Some(0);
+
+    fn required_size(&self) -> usize {
+        0
+    }
+
+    fn write(&self, _writer: &mut BytesWriter) {}
+
+    fn read(_reader: &mut BytesReader) -> Result<Self, BytesError> {
+        Ok(())
+    }
+}
+
+impl<T> Bytes for PhantomData<T>
+where
+    T: ?Sized,
+{
+    const FIXED_SIZE: Option<usize> = Some(0);
+
+    fn required_size(&self) -> usize {
+        0
+    }
+
+    fn write(&self, _writer: &mut BytesWriter) {}
+
+    fn read(_reader: &mut BytesReader) -> Result<Self, BytesError> {
+        Ok(PhantomData)
+    }
+}
+
+impl Bytes for Infallible {
+    fn required_size(&self) -> usize {
+        match *self {}
+    }
+
+    fn write(&self, _writer: &mut BytesWriter) {
+        match *self {}
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        Err(BytesError::InvalidDiscriminant(u8::read(reader)? as u64))
+    }
+}
+
+impl<T> Bytes for Option<T>
+where
+    T: Bytes,
+{
+    fn required_size(&self) -> usize {
+        1 + self.as_ref().map_or(0, |value| value.required_size())
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        match self {
+            None => 0u8.write(writer),
+            Some(value) => {
+                1u8.write(writer);
+                value.write(writer);
+            }
+        }
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        match u8::read(reader)? {
+            0 => Ok(None),
+            1 => Ok(Some(T::read(reader)?)),
+            tag => Err(BytesError::InvalidOptionTag(tag)),
+        }
+    }
+}
+
+impl Bytes for String {
+    fn required_size(&self) -> usize {
+        8 + self.len()
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        let len = self.len();
+        (len as u64).write(writer);
+
+        writer.write(self.as_bytes());
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        let len = reader.read_len::<u8>()?;
+        let pos = reader.read;
+        let bytes = reader.read_vec::<String>(len)?;
+        String::from_utf8(bytes).map_err(|_| BytesError::InvalidUtf8(pos))
+    }
+}
+
+impl Bytes for Box<str> {
+    fn required_size(&self) -> usize {
+        8 + self.len()
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        let len = self.len();
+        (len as u64).write(writer);
+
+        writer.write(self.as_bytes());
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        String::read(reader).map(String::into_boxed_str)
+    }
+}
+
+impl<T> Bytes for Cow<'_, [T]>
+where
+    T: Bytes + Clone,
+{
+    fn required_size(&self) -> usize {
+        8 + elems_size(self.iter())
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        let len = self.len();
+        (len as u64).write(writer);
+
+        write_elems(self, writer);
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        Vec::read(reader).map(Cow::Owned)
+    }
+}
+
+impl Bytes for Cow<'_, str> {
+    fn required_size(&self) -> usize {
+        8 + self.len()
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        let len = self.len();
+        (len as u64).write(writer);
+
+        writer.write(self.as_bytes());
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        String::read(reader).map(Cow::Owned)
+    }
+}
+
 define_derive_deftly! {
     export Bytes:
 
+    ${defcond TAGGED tmeta(bytes(tagged))}
+    ${defcond TLV tmeta(bytes(tlv))}
+    ${defcond KEYED any(TAGGED, TLV)}
+    ${if all(TAGGED, not(is_struct)) {
+        ${error "tagged encoding is only supported for structs"}
+    }}
+    ${if all(TLV, not(is_struct)) {
+        ${error "TLV encoding is only supported for structs"}
+    }}
+    ${if all(TAGGED, TLV) {
+        ${error "tagged and TLV encodings are mutually exclusive"}
+    }}
+    ${if all(tmeta(bytes(repr)), not(is_enum)) {
+        ${error "discriminant types are only supported for enums"}
+    }}
+    // Fields of tagged structs with a default are still encoded, and only fall back to the default
+    // if they are missing, such as when reading data written before they were added:
+    ${defcond SKIPPED any(fmeta(bytes(skip)), all(fmeta(bytes(default)), not(TAGGED)))}
+    ${define REPR {
+        ${if tmeta(bytes(repr)) { ${tmeta(bytes(repr)) as ty} } else { u8 }}
+    }}
+    ${define TYPE_NAME {
+        ${if is_enum {
+            concat!(stringify!($tname), "::", stringify!($vname))
+        } else {
+            stringify!($tname)
+        }}
+    }}
+    ${define VERSION {
+        ${if tmeta(bytes(version)) { ${tmeta(bytes(version)) as expr} } else { 0 }}
+    }}
+    ${define DEFAULT {
+        ${if fmeta(bytes(default)) { ${fmeta(bytes(default)) as expr} } else { Default::default() }}
+    }}
+    ${define CODEC {
+        ${if fmeta(bytes(with)) {
+            ${fmeta(bytes(with)) as path}
+        } else {
+            <$ftype as $crate::Bytes>
+        }}
+    }}
+    ${define WIRE_TYPE {
+        ${if fmeta(bytes(with)) {
+            $crate::tlv::WireType::Variable
+        } else {
+            <$ftype as $crate::Bytes>::WIRE_TYPE
+        }}
+    }}
+    ${define FIELD_COUNT {
+        0 ${for fields { ${when not(SKIPPED)} + 1 }}
+    }}
+    ${define READ_FIELD {
+        $CODEC::read(reader).map_err(|err| err.in_field($TYPE_NAME, stringify!($fname)))?
+    }}
+    ${define FIELD_VALUE {
+        ${if SKIPPED {
+            $DEFAULT
+        } else if all(TAGGED, fmeta(bytes(default))) {
+            $fpatname.unwrap_or_else(|| $DEFAULT)
+        } else if TAGGED {
+            $fpatname.ok_or_else(|| {
+                $crate::BytesError::MissingField.in_field($TYPE_NAME, stringify!($fname))
+            })?
+        } else if TLV {
+            $fpatname.unwrap_or_default()
+        } else {
+            $READ_FIELD
+        }}
+    }}
+    ${define CONSTRUCT {
+        ${if v_is_unit {
+            $vtype
+        } else if v_is_tuple {
+            $vtype(${for fields { $FIELD_VALUE, }})
+        } else {
+            $vtype { ${for fields { $fname: $FIELD_VALUE, }} }
+        }}
+    }}
+
+    // Rejects duplicate and overflowing discriminants at compile time, which could otherwise make
+    // variants unreadable:
+    ${if all(is_enum, not(is_empty($($vname)))) {
+        const _: () = {
+            let mut discriminants = [0; 0 ${for variants { + 1 }}];
+            let mut discriminant: $REPR = 0;
+            let mut i = 0;
+            $(
+                ${if vmeta(bytes(discriminant)) {
+                    discriminant = ${vmeta(bytes(discriminant)) as expr};
+                } else {
+                    if i > 0 {
+                        discriminant += 1;
+                    }
+                }}
+                discriminants[i] = discriminant as u128;
+                i += 1;
+            )
+            $crate::enums::check_discriminants(&discriminants);
+        };
+    }}
+
+    // Field bounds are only needed for generic types. Leaving them out otherwise lets recursive
+    // types like `enum List { Nil, Cons(u8, Box<List>) }` derive the trait, whose bounds would
+    // otherwise depend on themselves.
     impl<$tgens> $crate::Bytes for $ttype
     where
-        $($ftype: $crate::Bytes,)
+        $twheres
+        ${if not(is_empty($tgens)) {
+            $(${when not(any(SKIPPED, fmeta(bytes(with))))} $ftype: $crate::Bytes,)
+            $(${when all(fmeta(bytes(skip)), not(fmeta(bytes(default))))} $ftype: Default,)
+            ${if TLV { $(${when not(SKIPPED)} $ftype: Default,) }}
+        }}
     {
+        ${if not(any(KEYED, is_enum)) {
+            const FIXED_SIZE: Option<usize> = $crate::fixed::sum(&[$(
+                ${when not(SKIPPED)}
+                ${if fmeta(bytes(with)) { None } else { <$ftype as $crate::Bytes>::FIXED_SIZE }},
+            )]);
+        }}
+        ${if TLV {
+            const WIRE_TYPE: $crate::tlv::WireType = $crate::tlv::WireType::Message;
+        }}
+
+        #[allow(unused)]
+        #[inline]
         fn required_size(&self) -> usize {
-            let mut size = 0;
-            $(
-                size += <$ftype as $crate::Bytes>::required_size(&self.$fname);
-            )
-            size
+            ${if TAGGED {
+                let mut size = $crate::tagged::HEADER_SIZE;
+                $(
+                    ${when not(SKIPPED)}
+                    size += $crate::tagged::FIELD_HEADER_SIZE + $CODEC::required_size(&self.$fname);
+                )
+                size
+            } else if TLV {
+                let count = $crate::VarInt::<usize>($FIELD_COUNT);
+                let mut size = <$crate::VarInt<usize> as $crate::Bytes>::required_size(&count);
+                let mut tag: u16 = 0;
+                $(
+                    ${when not(SKIPPED)}
+                    ${if fmeta(bytes(tag)) {
+                        tag = ${fmeta(bytes(tag)) as expr};
+                    }}
+                    let len = $CODEC::required_size(&self.$fname);
+                    size += $crate::tlv::field_header_size(tag, len) + len;
+                    tag += 1;
+                )
+                size
+            } else if all(is_enum, is_empty($($vname))) {
+                match *self {}
+            } else if is_enum {
+                match self {
+                    $(
+                        $vpat => {
+                            let mut size = <$REPR as $crate::Bytes>::required_size(&0);
+                            $(
+                                ${when not(SKIPPED)}
+                                size += $CODEC::required_size($fpatname);
+                            )
+                            size
+                        }
+                    )
+                }
+            } else {
+                let mut size = 0;
+                $(
+                    ${when not(SKIPPED)}
+                    size += $CODEC::required_size(&self.$fname);
+                )
+                size
+            }}
         }
 
         #[allow(unused)]
+        #[inline]
         fn write(&self, writer: &mut $crate::BytesWriter) {
-            $(<$ftype as $crate::Bytes>::write(&self.$fname, writer);)
+            ${if TAGGED {
+                <u8 as $crate::Bytes>::write(&$VERSION, writer);
+                let count: u64 = $FIELD_COUNT;
+                <u64 as $crate::Bytes>::write(&count, writer);
+                let mut tag: u16 = 0;
+                $(
+                    ${when not(SKIPPED)}
+                    ${if fmeta(bytes(tag)) {
+                        tag = ${fmeta(bytes(tag)) as expr};
+                    }}
+                    <u16 as $crate::Bytes>::write(&tag, writer);
+                    <usize as $crate::Bytes>::write(&$CODEC::required_size(&self.$fname), writer);
+                    $CODEC::write(&self.$fname, writer);
+                    tag += 1;
+                )
+            } else if TLV {
+                let count = $crate::VarInt::<usize>($FIELD_COUNT);
+                <$crate::VarInt<usize> as $crate::Bytes>::write(&count, writer);
+                let mut tag: u16 = 0;
+                $(
+                    ${when not(SKIPPED)}
+                    ${if fmeta(bytes(tag)) {
+                        tag = ${fmeta(bytes(tag)) as expr};
+                    }}
+                    let len = $CODEC::required_size(&self.$fname);
+                    $crate::tlv::write_field_header(tag, $WIRE_TYPE, len, writer);
+                    $CODEC::write(&self.$fname, writer);
+                    tag += 1;
+                )
+            } else if is_enum {
+                let mut discriminant: $REPR = 0;
+                $(
+                    ${if vmeta(bytes(discriminant)) {
+                        discriminant = ${vmeta(bytes(discriminant)) as expr};
+                    }}
+                    if let $vpat = self {
+                        <$REPR as $crate::Bytes>::write(&discriminant, writer);
+                        $(
+                            ${when not(SKIPPED)}
+                            $CODEC::write($fpatname, writer);
+                        )
+                        return;
+                    }
+                    discriminant = discriminant.wrapping_add(1);
+                )
+            } else {
+                $(
+                    ${when not(SKIPPED)}
+                    $CODEC::write(&self.$fname, writer);
+                )
+            }}
         }
 
-        #[allow(unused)]
+        // Clippy mistakes the variant names in errors for a literal that could be written out.
+        #[allow(unused, clippy::useless_concat)]
+        #[inline]
         fn read(reader: &mut $crate::BytesReader) -> Result<Self, $crate::BytesError> {
-            Ok(Self {
-                $($fname: <$ftype as $crate::Bytes>::read(reader)?,)
-            })
+            let pos = reader.position();
+            let output = reader.nested(|reader| {
+                ${if TAGGED {
+                    $crate::tagged::read_version(reader, $VERSION)?;
+                    let count = <u64 as $crate::Bytes>::read(reader)?;
+                    $(
+                        ${when not(SKIPPED)}
+                        let mut $fpatname: Option<$ftype> = None;
+                    )
+                    for _ in 0..count {
+                        let field_pos = reader.position();
+                        let field_tag = <u16 as $crate::Bytes>::read(reader)?;
+                        let len = <usize as $crate::Bytes>::read(reader)?;
+                        let mut known = false;
+                        let mut tag: u16 = 0;
+                        $(
+                            ${when not(SKIPPED)}
+                            ${if fmeta(bytes(tag)) {
+                                tag = ${fmeta(bytes(tag)) as expr};
+                            }}
+                            if field_tag == tag {
+                                if $fpatname.is_some() {
+                                    return Err($crate::BytesError::DuplicateField(field_pos));
+                                }
+                                let read = |reader: &mut $crate::BytesReader| $CODEC::read(reader);
+                                $fpatname = Some(
+                                    $crate::tagged::read_field(reader, len, read)
+                                        .map_err(|err| {
+                                            err.in_field($TYPE_NAME, stringify!($fname))
+                                        })?,
+                                );
+                                known = true;
+                            }
+                            tag += 1;
+                        )
+                        if !known {
+                            $crate::tagged::skip_field(reader, len)?;
+                        }
+                    }
+                    Ok($CONSTRUCT)
+                } else if TLV {
+                    let count = <$crate::VarInt<usize> as $crate::Bytes>::read(reader)?.0;
+                    $(
+                        ${when not(SKIPPED)}
+                        let mut $fpatname: Option<$ftype> = None;
+                    )
+                    for _ in 0..count {
+                        let header = $crate::tlv::read_field_header(reader)?;
+                        let mut known = false;
+                        let mut tag: u16 = 0;
+                        $(
+                            ${when not(SKIPPED)}
+                            ${if fmeta(bytes(tag)) {
+                                tag = ${fmeta(bytes(tag)) as expr};
+                            }}
+                            if header.tag == tag {
+                                let read = |reader: &mut $crate::BytesReader| $CODEC::read(reader);
+                                $fpatname = Some(
+                                    $crate::tlv::read_field(reader, &header, $WIRE_TYPE, read)
+                                        .map_err(|err| {
+                                            err.in_field($TYPE_NAME, stringify!($fname))
+                                        })?,
+                                );
+                                known = true;
+                            }
+                            tag += 1;
+                        )
+                        if !known {
+                            $crate::tagged::skip_field(reader, header.len)?;
+                        }
+                    }
+                    Ok($CONSTRUCT)
+                } else if is_enum {
+                    let discriminant = <$REPR as $crate::Bytes>::read(reader)?;
+                    let mut expected: $REPR = 0;
+                    $(
+                        ${if vmeta(bytes(discriminant)) {
+                            expected = ${vmeta(bytes(discriminant)) as expr};
+                        }}
+                        if discriminant == expected {
+                            return Ok($CONSTRUCT);
+                        }
+                        expected = expected.wrapping_add(1);
+                    )
+                    Err($crate::BytesError::InvalidDiscriminant(discriminant as u64))
+                } else {
+                    Ok($CONSTRUCT)
+                }}
+            })?;
+
+            ${if tmeta(bytes(validate)) {
+                ${tmeta(bytes(validate)) as path}(&output).map_err(|err| {
+                    $crate::BytesError::Invalid {
+                        pos,
+                        source: Box::new(err),
+                    }
+                })?;
+            }}
+
+            Ok(output)
         }
     }
 }
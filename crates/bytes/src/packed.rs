@@ -0,0 +1,226 @@
+use crate::{Bytes, BytesError, BytesReader, BytesWriter, FixedBytes, fixed};
+
+// Packs sequences of small values into `BITS` bits each, least significant bit first. The last
+// byte is padded with zero bits, which readers require, so that every sequence has exactly one
+// valid encoding. Values are checked to fit when the sequence is constructed, so that writing it
+// can't fail.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Packed<T, const BITS: u32>(pub(crate) T);
+
+impl<T, const BITS: u32> Packed<T, BITS> {
+    pub fn new<E>(elems: T) -> Result<Self, BytesError>
+    where
+        T: AsRef<[E]>,
+        E: PackedElem,
+    {
+        const { assert!(BITS > 0 && BITS <= E::BITS, "invalid number of bits") };
+
+        match elems
+            .as_ref()
+            .iter()
+            .position(|elem| elem.to_bits().checked_shr(BITS).unwrap_or(0) != 0)
+        {
+            Some(index) => Err(BytesError::ValueTooWide { index, bits: BITS }),
+            None => Ok(Self(elems)),
+        }
+    }
+
+    pub fn get(&self) -> &T {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+pub trait PackedElem: Copy {
+    const BITS: u32;
+
+    fn to_bits(self) -> u64;
+    fn from_bits(bits: u64) -> Result<Self, BytesError>;
+}
+
+macro_rules! impl_packed_elem_for_int {
+    ($($ty:ty),+) => {
+        $(
+            impl PackedElem for $ty {
+                const BITS: u32 = <$ty>::BITS;
+
+                fn to_bits(self) -> u64 {
+                    self as u64
+                }
+
+                fn from_bits(bits: u64) -> Result<Self, BytesError> {
+                    Ok(bits as $ty)
+                }
+            }
+        )+
+    };
+}
+
+impl_packed_elem_for_int!(u8, u16, u32, u64);
+
+impl PackedElem for usize {
+    const BITS: u32 = u64::BITS;
+
+    fn to_bits(self) -> u64 {
+        self as u64
+    }
+
+    fn from_bits(bits: u64) -> Result<Self, BytesError> {
+        bits.try_into().map_err(|_| BytesError::UsizeTooSmall)
+    }
+}
+
+impl PackedElem for bool {
+    const BITS: u32 = 1;
+
+    fn to_bits(self) -> u64 {
+        self as u64
+    }
+
+    fn from_bits(bits: u64) -> Result<Self, BytesError> {
+        match bits {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(BytesError::InvalidBool(value as u8)),
+        }
+    }
+}
+
+const fn packed_size(len: usize, bits: u32) -> usize {
+    (len * bits as usize).div_ceil(8)
+}
+
+fn pack<T, const BITS: u32>(elems: &[T]) -> Vec<u8>
+where
+    T: PackedElem,
+{
+    const { assert!(BITS > 0 && BITS <= T::BITS, "invalid number of bits") };
+
+    let mut bytes = Vec::with_capacity(packed_size(elems.len(), BITS));
+    let mut acc = 0u128;
+    let mut filled = 0;
+    for elem in elems {
+        let value = elem.to_bits();
+        debug_assert!(value.checked_shr(BITS).unwrap_or(0) == 0);
+
+        acc |= (value as u128) << filled;
+        filled += BITS;
+        while filled >= 8 {
+            bytes.push(acc as u8);
+            acc >>= 8;
+            filled -= 8;
+        }
+    }
+    if filled > 0 {
+        bytes.push(acc as u8);
+    }
+
+    bytes
+}
+
+fn unpack<T, const BITS: u32>(reader: &mut BytesReader, len: usize) -> Result<Vec<T>, BytesError>
+where
+    T: PackedElem,
+{
+    const { assert!(BITS > 0 && BITS <= T::BITS, "invalid number of bits") };
+
+    let size = len
+        .checked_mul(BITS as usize)
+        .ok_or(BytesError::UsizeTooSmall)?
+        .div_ceil(8);
+    let pos = reader.read;
+    let bytes = reader.read_vec::<[T]>(size)?;
+
+    let mask = u64::MAX >> (u64::BITS - BITS);
+    let mut bytes = bytes.into_iter();
+    let mut elems = Vec::with_capacity(len);
+    let mut acc = 0u128;
+    let mut filled = 0;
+    for _ in 0..len {
+        while filled < BITS {
+            let byte = bytes.next().expect("packed data is long enough");
+            acc |= (byte as u128) << filled;
+            filled += 8;
+        }
+
+        elems.push(T::from_bits(acc as u64 & mask)?);
+        acc >>= BITS;
+        filled -= BITS;
+    }
+    if acc != 0 {
+        return Err(BytesError::NonZeroPadding(pos + size - 1));
+    }
+
+    Ok(elems)
+}
+
+impl<T, const BITS: u32> Bytes for Packed<Vec<T>, BITS>
+where
+    T: PackedElem,
+{
+    fn required_size(&self) -> usize {
+        8 + packed_size(self.0.len(), BITS)
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        let len = self.0.len();
+        (len as u64).write(writer);
+
+        writer.write(&pack::<T, BITS>(&self.0));
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        let len = reader.read_len::<T>()?;
+        unpack::<T, BITS>(reader, len).map(Self)
+    }
+}
+
+impl<T, const BITS: u32> Bytes for Packed<Box<[T]>, BITS>
+where
+    T: PackedElem,
+{
+    fn required_size(&self) -> usize {
+        8 + packed_size(self.0.len(), BITS)
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        let len = self.0.len();
+        (len as u64).write(writer);
+
+        writer.write(&pack::<T, BITS>(&self.0));
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        Packed::<Vec<T>, BITS>::read(reader).map(|elems| Self(elems.0.into_boxed_slice()))
+    }
+}
+
+impl<const N: usize, T, const BITS: u32> Bytes for Packed<[T; N], BITS>
+where
+    T: PackedElem,
+{
+    const FIXED_SIZE: Option<usize> = Some(packed_size(N, BITS));
+
+    fn required_size(&self) -> usize {
+        packed_size(N, BITS)
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        writer.write(&pack::<T, BITS>(&self.0));
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        let elems = unpack::<T, BITS>(reader, N)?;
+        Ok(Self(elems.try_into().unwrap_or_else(|_| unreachable!())))
+    }
+}
+
+impl<const N: usize, T, const BITS: u32> FixedBytes for Packed<[T; N], BITS>
+where
+    T: PackedElem,
+{
+    const SIZE: usize = fixed::size::<Self>();
+}
@@ -0,0 +1,65 @@
+use rayon::prelude::*;
+
+use crate::{Bytes, BytesError, BytesReader, FixedBytes, ReadLimits, ReaderBackend};
+
+// Below this many bytes of elements, splitting the work costs more than it saves:
+const PAR_THRESHOLD: usize = 64 * 1024;
+
+// Decodes sequences of fixed-size elements in parallel. Since every element has the same size,
+// the data can be split by that stride up front, and each element decoded independently.
+pub trait ParFromBytes: Bytes + Sized {
+    fn par_from_bytes(bytes: &[u8]) -> Result<Self, BytesError> {
+        Self::par_from_bytes_with_limits(bytes, ReadLimits::default())
+    }
+
+    fn par_from_bytes_with_limits(bytes: &[u8], limits: ReadLimits) -> Result<Self, BytesError>;
+}
+
+impl<T> ParFromBytes for Vec<T>
+where
+    T: FixedBytes + Send,
+{
+    fn par_from_bytes_with_limits(bytes: &[u8], limits: ReadLimits) -> Result<Self, BytesError> {
+        let mut reader = BytesReader::with_limits(bytes, limits);
+        let len = reader.read_elems_len::<T>()?;
+        let start = reader.read;
+        let end = start + len * T::SIZE;
+
+        // Byte sequences are copied in one call, and zero-sized elements can't be split by their
+        // size, so neither is worth decoding in parallel:
+        if T::SIZE == 0 || end - start < PAR_THRESHOLD || T::as_byte_slice(&[]).is_some() {
+            return Self::from_bytes_with_limits(bytes, limits);
+        }
+
+        if end < bytes.len() {
+            return Err(BytesError::TrailingData(end));
+        }
+
+        // Each element is read from its own reader over the shared data, so that positions in
+        // errors are still relative to the start of the data:
+        let (allocated, depth) = (reader.allocated, reader.depth);
+        (0..len)
+            .into_par_iter()
+            .map(|i| {
+                let pos = start + i * T::SIZE;
+                let mut reader = BytesReader {
+                    backend: ReaderBackend::Slice(&bytes[..pos + T::SIZE]),
+                    read: pos,
+                    limits,
+                    allocated,
+                    depth,
+                };
+                T::read(&mut reader)
+            })
+            .collect()
+    }
+}
+
+impl<T> ParFromBytes for Box<[T]>
+where
+    T: FixedBytes + Send,
+{
+    fn par_from_bytes_with_limits(bytes: &[u8], limits: ReadLimits) -> Result<Self, BytesError> {
+        Vec::par_from_bytes_with_limits(bytes, limits).map(Vec::into_boxed_slice)
+    }
+}
@@ -0,0 +1,78 @@
+use std::marker::PhantomData;
+
+use chacha20poly1305::{ChaCha20Poly1305, Key, KeyInit, Nonce, aead::Aead};
+
+use crate::{Bytes, BytesError, BytesReader, BytesWriter, ReadLimits};
+
+pub const KEY_SIZE: usize = 32;
+pub const NONCE_SIZE: usize = 12;
+
+// The encoding of a value, encrypted and authenticated with ChaCha20-Poly1305 under a key supplied
+// by the caller. It is encoded as the nonce followed by the ciphertext and its tag, so it can be
+// sent and received like any other value, and only opened by someone holding the key.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Sealed<T> {
+    nonce: [u8; NONCE_SIZE],
+    ciphertext: Box<[u8]>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Sealed<T>
+where
+    T: Bytes,
+{
+    pub fn seal(value: &T, key: &[u8; KEY_SIZE]) -> Self {
+        Self::seal_with_nonce(value, key, rand::random())
+    }
+
+    // Nonces must never be reused with the same key, which `seal` guarantees by picking them at
+    // random. This is only meant for deterministic encodings, such as test vectors.
+    pub fn seal_with_nonce(value: &T, key: &[u8; KEY_SIZE], nonce: [u8; NONCE_SIZE]) -> Self {
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), &*value.to_bytes())
+            .expect("plaintext is not too long");
+
+        Self {
+            nonce,
+            ciphertext: ciphertext.into_boxed_slice(),
+            marker: PhantomData,
+        }
+    }
+
+    pub fn open(&self, key: &[u8; KEY_SIZE]) -> Result<T, BytesError> {
+        self.open_with_limits(key, ReadLimits::default())
+    }
+
+    pub fn open_with_limits(
+        &self,
+        key: &[u8; KEY_SIZE],
+        limits: ReadLimits,
+    ) -> Result<T, BytesError> {
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+        let bytes = cipher
+            .decrypt(Nonce::from_slice(&self.nonce), &*self.ciphertext)
+            .map_err(|_| BytesError::InvalidSeal)?;
+
+        T::from_bytes_with_limits(&bytes, limits)
+    }
+}
+
+impl<T> Bytes for Sealed<T> {
+    fn required_size(&self) -> usize {
+        self.nonce.required_size() + self.ciphertext.required_size()
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        self.nonce.write(writer);
+        self.ciphertext.write(writer);
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        Ok(Self {
+            nonce: Bytes::read(reader)?,
+            ciphertext: Bytes::read(reader)?,
+            marker: PhantomData,
+        })
+    }
+}
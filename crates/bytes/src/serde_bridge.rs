@@ -0,0 +1,77 @@
+use std::{
+    fmt::{self, Formatter},
+    marker::PhantomData,
+};
+
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, SeqAccess, Visitor},
+};
+
+use crate::Bytes;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SerdeAsBytes<T>(pub T);
+
+impl<T> Serialize for SerdeAsBytes<T>
+where
+    T: Bytes,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&self.0.to_bytes())
+    }
+}
+
+impl<'de, T> Deserialize<'de> for SerdeAsBytes<T>
+where
+    T: Bytes,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(BytesVisitor(PhantomData))
+    }
+}
+
+const MAX_PREALLOC: usize = 1024 * 1024;
+
+struct BytesVisitor<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for BytesVisitor<T>
+where
+    T: Bytes,
+{
+    type Value = SerdeAsBytes<T>;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "a byte encoding")
+    }
+
+    fn visit_bytes<E>(self, bytes: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        T::from_bytes(bytes).map(SerdeAsBytes).map_err(E::custom)
+    }
+
+    // Self-describing formats such as JSON have no native byte strings, and represent them as
+    // sequences of integers instead:
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        // The size hint comes from the input, so it's capped like serde's own collections do,
+        // instead of letting it decide how much to allocate up front:
+        let capacity = seq.size_hint().unwrap_or(0).min(MAX_PREALLOC);
+        let mut bytes = Vec::with_capacity(capacity);
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+
+        self.visit_bytes(&bytes)
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{Bytes, BytesError, BytesReader};
+
+// Helpers for the tagged encoding generated by the `Bytes` derive template. Each field is encoded
+// as its tag, the length of its payload, and the payload itself, which lets readers skip fields
+// they don't know about.
+
+pub const HEADER_SIZE: usize = 1 + 8;
+pub const FIELD_HEADER_SIZE: usize = 2 + 8;
+
+// Data written by an older version of a type is still readable, since its fields are tagged, but
+// a newer version may have changed the meaning of the fields.
+pub fn read_version(reader: &mut BytesReader, current: u8) -> Result<u8, BytesError> {
+    match u8::read(reader)? {
+        version if version <= current => Ok(version),
+        version => Err(BytesError::UnsupportedVersion(version)),
+    }
+}
+
+pub fn read_field<'a, T, F>(
+    reader: &mut BytesReader<'a>,
+    len: usize,
+    read: F,
+) -> Result<T, BytesError>
+where
+    F: FnOnce(&mut BytesReader<'a>) -> Result<T, BytesError>,
+{
+    let pos = reader.read;
+    let value = read(reader)?;
+    if reader.read - pos != len {
+        return Err(BytesError::FieldLengthMismatch {
+            pos,
+            expected: len,
+            actual: reader.read - pos,
+        });
+    }
+
+    Ok(value)
+}
+
+pub fn skip_field(reader: &mut BytesReader, len: usize) -> Result<(), BytesError> {
+    reader.skip::<[u8]>(len)
+}
@@ -0,0 +1,117 @@
+use std::fmt::Debug;
+
+use proptest::{prelude::*, sample::Index};
+
+use crate::{Bytes, ReadLimits};
+
+#[macro_export]
+macro_rules! assert_roundtrip {
+    ($value:expr) => {
+        $crate::testing::check_roundtrip(&$value)
+    };
+}
+
+pub fn check_roundtrip<T>(value: &T)
+where
+    T: Bytes + PartialEq + Debug,
+{
+    let bytes = value.to_bytes();
+    assert_eq!(bytes.len(), value.required_size(), "required size mismatch");
+    assert_eq!(&T::from_bytes(&bytes).expect("decoding failed"), value);
+
+    let mut streamed = Vec::new();
+    value.write_to(&mut streamed).expect("streaming failed");
+    assert_eq!(&*streamed, &*bytes, "streamed encoding mismatch");
+    assert_eq!(
+        &T::read_from(&*bytes).expect("streamed decoding failed"),
+        value
+    );
+
+    check_truncation::<T>(&bytes);
+    check_trailing_data::<T>(&bytes);
+    check_corruption::<T>(&bytes);
+}
+
+pub fn check_truncation<T>(bytes: &[u8])
+where
+    T: Bytes + Debug,
+{
+    for len in 0..bytes.len() {
+        let truncated = &bytes[..len];
+        let output =
+            T::from_bytes_with_limits(truncated, ReadLimits::proportional(truncated.len()));
+        assert!(
+            output.is_err(),
+            "decoding truncated data succeeded: {output:?}"
+        );
+    }
+}
+
+pub fn check_trailing_data<T>(bytes: &[u8])
+where
+    T: Bytes + Debug,
+{
+    let mut extended = bytes.to_vec();
+    extended.push(0);
+
+    let output = T::from_bytes(&extended);
+    assert!(
+        output.is_err(),
+        "decoding trailing data succeeded: {output:?}"
+    );
+}
+
+pub fn check_corruption<T>(bytes: &[u8])
+where
+    T: Bytes,
+{
+    let mut corrupted = bytes.to_vec();
+    for i in 0..corrupted.len() {
+        for mask in [0x01, 0x80, 0xff] {
+            corrupted[i] ^= mask;
+            let _ =
+                T::from_bytes_with_limits(&corrupted, ReadLimits::proportional(corrupted.len()));
+            corrupted[i] ^= mask;
+        }
+    }
+}
+
+pub fn check_no_panic<T>(bytes: &[u8])
+where
+    T: Bytes,
+{
+    let _ = T::from_bytes_lossy(bytes);
+}
+
+pub fn mutations(bytes: Box<[u8]>) -> impl Strategy<Value = Vec<u8>> {
+    let len = bytes.len();
+    let truncated = {
+        let bytes = bytes.clone();
+        (0..=len).prop_map(move |len| bytes[..len].to_vec())
+    };
+    let extended = {
+        let bytes = bytes.clone();
+        prop::collection::vec(any::<u8>(), 1..16).prop_map(move |extra| {
+            let mut extended = bytes.to_vec();
+            extended.extend(extra);
+            extended
+        })
+    };
+    let corrupted = (any::<Index>(), 1..=u8::MAX).prop_map(move |(index, mask)| {
+        let mut corrupted = bytes.to_vec();
+        if !corrupted.is_empty() {
+            corrupted[index.index(len)] ^= mask;
+        }
+        corrupted
+    });
+
+    prop_oneof![truncated, extended, corrupted]
+}
+
+pub fn mutated<S>(values: S) -> impl Strategy<Value = Vec<u8>>
+where
+    S: Strategy,
+    S::Value: Bytes,
+{
+    values.prop_flat_map(|value| mutations(value.to_bytes()))
+}
@@ -0,0 +1,86 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+#[cfg(feature = "base64")]
+use base64::{DecodeError, Engine, engine::general_purpose::STANDARD};
+
+use crate::{Bytes, BytesError};
+
+// Wrappers that display a value as the text encoding of its binary encoding, and parse it back.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HexBytes<T>(pub T);
+
+#[cfg(feature = "base64")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Base64Bytes<T>(pub T);
+
+impl<T> Display for HexBytes<T>
+where
+    T: Bytes,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for byte in self.0.to_bytes() {
+            write!(f, "{byte:02x}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> FromStr for HexBytes<T>
+where
+    T: Bytes,
+{
+    type Err = BytesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.as_bytes();
+        if !digits.len().is_multiple_of(2) {
+            return Err(BytesError::InvalidHex(digits.len()));
+        }
+
+        let digit = |pos: usize| {
+            (digits[pos] as char)
+                .to_digit(16)
+                .ok_or(BytesError::InvalidHex(pos))
+        };
+        let bytes = (0..digits.len())
+            .step_by(2)
+            .map(|pos| Ok((digit(pos)? << 4 | digit(pos + 1)?) as u8))
+            .collect::<Result<Vec<_>, BytesError>>()?;
+
+        T::from_bytes(&bytes).map(Self)
+    }
+}
+
+#[cfg(feature = "base64")]
+impl<T> Display for Base64Bytes<T>
+where
+    T: Bytes,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&STANDARD.encode(self.0.to_bytes()))
+    }
+}
+
+#[cfg(feature = "base64")]
+impl<T> FromStr for Base64Bytes<T>
+where
+    T: Bytes,
+{
+    type Err = BytesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = STANDARD.decode(s).map_err(|err| {
+            BytesError::InvalidBase64(match err {
+                DecodeError::InvalidByte(pos, _) | DecodeError::InvalidLastSymbol(pos, _) => pos,
+                DecodeError::InvalidLength(_) | DecodeError::InvalidPadding => s.len(),
+            })
+        })?;
+
+        T::from_bytes(&bytes).map(Self)
+    }
+}
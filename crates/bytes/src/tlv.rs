@@ -0,0 +1,145 @@
+use crate::{Bytes, BytesError, BytesReader, BytesWriter, VarInt, tagged};
+
+// Helpers for the TLV encoding generated by the `Bytes` derive template. Like the tagged encoding,
+// each field carries its tag and the length of its payload, but also the wire type of the payload,
+// so that messages can be walked with `RawMessage` without knowing their definitions. Counts, tags
+// and lengths are encoded as `VarInt`s to keep the per-field overhead small.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WireType {
+    // A payload of the same size for every value of its type.
+    Fixed,
+    // A payload whose size depends on its value.
+    Variable,
+    // A nested TLV-encoded message.
+    Message,
+}
+
+impl WireType {
+    pub const fn of(fixed_size: Option<usize>) -> Self {
+        match fixed_size {
+            Some(_) => Self::Fixed,
+            None => Self::Variable,
+        }
+    }
+}
+
+impl Bytes for WireType {
+    const FIXED_SIZE: Option<usize> = u8::FIXED_SIZE;
+
+    fn required_size(&self) -> usize {
+        size_of::<u8>()
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        let value: u8 = match self {
+            Self::Fixed => 0,
+            Self::Variable => 1,
+            Self::Message => 2,
+        };
+        value.write(writer);
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        let pos = reader.read;
+        match u8::read(reader)? {
+            0 => Ok(Self::Fixed),
+            1 => Ok(Self::Variable),
+            2 => Ok(Self::Message),
+            _ => Err(BytesError::InvalidWireType(pos)),
+        }
+    }
+}
+
+pub struct FieldHeader {
+    pub tag: u16,
+    pub wire_type: WireType,
+    pub len: usize,
+    pos: usize,
+}
+
+pub fn field_header_size(tag: u16, len: usize) -> usize {
+    VarInt(tag).required_size() + size_of::<u8>() + VarInt(len).required_size()
+}
+
+pub fn write_field_header(tag: u16, wire_type: WireType, len: usize, writer: &mut BytesWriter) {
+    VarInt(tag).write(writer);
+    wire_type.write(writer);
+    VarInt(len).write(writer);
+}
+
+pub fn read_field_header(reader: &mut BytesReader) -> Result<FieldHeader, BytesError> {
+    let tag = VarInt::<u16>::read(reader)?.0;
+    let pos = reader.read;
+    let wire_type = WireType::read(reader)?;
+    let len = VarInt::<usize>::read(reader)?.0;
+
+    Ok(FieldHeader {
+        tag,
+        wire_type,
+        len,
+        pos,
+    })
+}
+
+pub fn read_field<'a, T, F>(
+    reader: &mut BytesReader<'a>,
+    header: &FieldHeader,
+    expected: WireType,
+    read: F,
+) -> Result<T, BytesError>
+where
+    F: FnOnce(&mut BytesReader<'a>) -> Result<T, BytesError>,
+{
+    if header.wire_type != expected {
+        return Err(BytesError::InvalidWireType(header.pos));
+    }
+
+    tagged::read_field(reader, header.len, read)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RawField {
+    pub tag: u16,
+    pub wire_type: WireType,
+    pub payload: Box<[u8]>,
+}
+
+// Any TLV-encoded message, as its fields in encoding order. The payloads of `WireType::Message`
+// fields can in turn be decoded as a `RawMessage`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RawMessage(pub Vec<RawField>);
+
+impl Bytes for RawMessage {
+    fn required_size(&self) -> usize {
+        let mut size = VarInt(self.0.len()).required_size();
+        for field in &self.0 {
+            size += field_header_size(field.tag, field.payload.len()) + field.payload.len();
+        }
+        size
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        VarInt(self.0.len()).write(writer);
+        for field in &self.0 {
+            write_field_header(field.tag, field.wire_type, field.payload.len(), writer);
+            writer.write(&field.payload);
+        }
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        let count = VarInt::<usize>::read(reader)?.0;
+        let mut fields = Vec::new();
+        for _ in 0..count {
+            let header = read_field_header(reader)?;
+            reader.reserve::<u8>(header.pos, header.len)?;
+            fields.push(RawField {
+                tag: header.tag,
+                wire_type: header.wire_type,
+                payload: reader.read_vec::<RawField>(header.len)?.into_boxed_slice(),
+            });
+        }
+
+        Ok(Self(fields))
+    }
+}
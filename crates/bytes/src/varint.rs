@@ -0,0 +1,255 @@
+use crate::{Bytes, BytesError, BytesReader, BytesWriter, elems_size, read_elems, write_elems};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VarInt<T>(pub T);
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VarLen<T>(pub T);
+
+// Encodes a signed integer as a `VarInt` of its zigzag mapping (0, -1, 1, -2, ... to 0, 1, 2,
+// 3, ...), so that values close to zero take few bytes regardless of their sign.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ZigZag<T>(pub T);
+
+// Codec functions for use with the `with` attribute of the `Bytes` derive template, which encode
+// an integer field as a `VarInt` without changing its type.
+
+pub fn required_size<T>(value: &T) -> usize
+where
+    T: Copy,
+    VarInt<T>: Bytes,
+{
+    VarInt(*value).required_size()
+}
+
+pub fn write<T>(value: &T, writer: &mut BytesWriter)
+where
+    T: Copy,
+    VarInt<T>: Bytes,
+{
+    VarInt(*value).write(writer);
+}
+
+pub fn read<T>(reader: &mut BytesReader) -> Result<T, BytesError>
+where
+    VarInt<T>: Bytes,
+{
+    VarInt::read(reader).map(|value| value.0)
+}
+
+// Codec functions like the above, which encode a signed integer field as a `ZigZag`.
+pub mod zigzag {
+    use super::ZigZag;
+    use crate::{Bytes, BytesError, BytesReader, BytesWriter};
+
+    pub fn required_size<T>(value: &T) -> usize
+    where
+        T: Copy,
+        ZigZag<T>: Bytes,
+    {
+        ZigZag(*value).required_size()
+    }
+
+    pub fn write<T>(value: &T, writer: &mut BytesWriter)
+    where
+        T: Copy,
+        ZigZag<T>: Bytes,
+    {
+        ZigZag(*value).write(writer);
+    }
+
+    pub fn read<T>(reader: &mut BytesReader) -> Result<T, BytesError>
+    where
+        ZigZag<T>: Bytes,
+    {
+        ZigZag::read(reader).map(|value| value.0)
+    }
+}
+
+fn varint_size(mut value: u128) -> usize {
+    let mut size = 1;
+    while value >= 0x80 {
+        value >>= 7;
+        size += 1;
+    }
+    size
+}
+
+fn write_varint(mut value: u128, writer: &mut BytesWriter) {
+    while value >= 0x80 {
+        writer.write(&[value as u8 | 0x80]);
+        value >>= 7;
+    }
+    writer.write(&[value as u8]);
+}
+
+fn read_varint(reader: &mut BytesReader, bits: u32) -> Result<u128, BytesError> {
+    let pos = reader.read;
+    let mut value = 0u128;
+    let mut shift = 0;
+    loop {
+        let byte = u8::read(reader)?;
+        let payload = (byte & 0x7f) as u128;
+
+        // Reject encodings that overflow the target type:
+        if shift >= bits || payload.checked_shr(bits - shift).unwrap_or(0) != 0 {
+            return Err(BytesError::InvalidVarInt(pos));
+        }
+        value |= payload << shift;
+
+        if byte & 0x80 == 0 {
+            // Reject non-minimal encodings, so that every value has exactly one valid encoding:
+            if byte == 0 && shift > 0 {
+                return Err(BytesError::InvalidVarInt(pos));
+            }
+            return Ok(value);
+        }
+        shift += 7;
+    }
+}
+
+fn read_len<T>(reader: &mut BytesReader) -> Result<usize, BytesError>
+where
+    T: Bytes,
+{
+    let pos = reader.read;
+    let len = read_varint(reader, u64::BITS)?
+        .try_into()
+        .map_err(|_| BytesError::UsizeTooSmall)?;
+    reader.reserve::<T>(pos, len)?;
+    reader.check_fixed_len::<T>(len)?;
+    Ok(len)
+}
+
+macro_rules! impl_bytes_for_varint {
+    ($ty:ty) => {
+        impl Bytes for VarInt<$ty> {
+            fn required_size(&self) -> usize {
+                varint_size(self.0 as u128)
+            }
+
+            fn write(&self, writer: &mut BytesWriter) {
+                write_varint(self.0 as u128, writer);
+            }
+
+            fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+                Ok(Self(read_varint(reader, <$ty>::BITS)? as $ty))
+            }
+        }
+    };
+}
+
+impl_bytes_for_varint!(u16);
+impl_bytes_for_varint!(u32);
+impl_bytes_for_varint!(u64);
+impl_bytes_for_varint!(u128);
+
+impl Bytes for VarInt<usize> {
+    fn required_size(&self) -> usize {
+        varint_size(self.0 as u128)
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        write_varint(self.0 as u128, writer);
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        read_varint(reader, u64::BITS)?
+            .try_into()
+            .map(Self)
+            .map_err(|_| BytesError::UsizeTooSmall)
+    }
+}
+
+macro_rules! impl_bytes_for_zigzag {
+    ($ty:ty, $unsigned:ty) => {
+        impl Bytes for ZigZag<$ty> {
+            fn required_size(&self) -> usize {
+                VarInt(self.encode()).required_size()
+            }
+
+            fn write(&self, writer: &mut BytesWriter) {
+                VarInt(self.encode()).write(writer);
+            }
+
+            fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+                VarInt::<$unsigned>::read(reader).map(|value| Self::decode(value.0))
+            }
+        }
+
+        impl ZigZag<$ty> {
+            fn encode(self) -> $unsigned {
+                ((self.0 << 1) ^ (self.0 >> (<$ty>::BITS - 1))) as $unsigned
+            }
+
+            fn decode(value: $unsigned) -> Self {
+                Self((value >> 1) as $ty ^ -((value & 1) as $ty))
+            }
+        }
+    };
+}
+
+impl_bytes_for_zigzag!(i16, u16);
+impl_bytes_for_zigzag!(i32, u32);
+impl_bytes_for_zigzag!(i64, u64);
+impl_bytes_for_zigzag!(i128, u128);
+impl_bytes_for_zigzag!(isize, usize);
+
+impl<T> Bytes for VarLen<Vec<T>>
+where
+    T: Bytes,
+{
+    fn required_size(&self) -> usize {
+        varint_size(self.0.len() as u128) + elems_size(self.0.iter())
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        write_varint(self.0.len() as u128, writer);
+
+        write_elems(&self.0, writer);
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        let len = read_len::<T>(reader)?;
+        read_elems(reader, len).map(Self)
+    }
+}
+
+impl<T> Bytes for VarLen<Box<[T]>>
+where
+    T: Bytes,
+{
+    fn required_size(&self) -> usize {
+        varint_size(self.0.len() as u128) + elems_size(self.0.iter())
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        write_varint(self.0.len() as u128, writer);
+
+        write_elems(&self.0, writer);
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        VarLen::<Vec<T>>::read(reader).map(|elems| Self(elems.0.into_boxed_slice()))
+    }
+}
+
+impl Bytes for VarLen<String> {
+    fn required_size(&self) -> usize {
+        varint_size(self.0.len() as u128) + self.0.len()
+    }
+
+    fn write(&self, writer: &mut BytesWriter) {
+        write_varint(self.0.len() as u128, writer);
+        writer.write(self.0.as_bytes());
+    }
+
+    fn read(reader: &mut BytesReader) -> Result<Self, BytesError> {
+        let len = read_len::<u8>(reader)?;
+        let pos = reader.read;
+        let bytes = reader.read_vec::<String>(len)?;
+        String::from_utf8(bytes)
+            .map(Self)
+            .map_err(|_| BytesError::InvalidUtf8(pos))
+    }
+}
@@ -0,0 +1,27 @@
+#![cfg(feature = "deflate")]
+
+use bytes::{Bytes, BytesError, Compressed, assert_roundtrip};
+
+#[test]
+fn roundtrip() {
+    assert_roundtrip!(Compressed(vec![7u8; 1000]));
+    assert_roundtrip!(Compressed(String::from("compressed")));
+}
+
+#[test]
+fn huge_declared_len() {
+    // Declares 2^40 uncompressed bytes, but holds an empty deflate block:
+    let mut bytes = Vec::new();
+    (1u64 << 40).write_into(&mut bytes);
+    2u64.write_into(&mut bytes);
+    bytes.extend([0x03, 0x00]);
+
+    assert!(matches!(
+        <Compressed<Vec<u8>>>::from_bytes(&bytes),
+        Err(BytesError::InvalidCompression(16))
+    ));
+
+    // And the largest length that fits, which can't be exceeded by one:
+    bytes[..8].copy_from_slice(&u64::MAX.to_le_bytes());
+    assert!(<Compressed<Vec<u8>>>::from_bytes(&bytes).is_err());
+}
@@ -0,0 +1,197 @@
+use bytes::{Bytes, BytesError, assert_roundtrip, derive_deftly_template_Bytes};
+use derive_deftly::Deftly;
+
+#[derive(Debug, PartialEq, Deftly)]
+#[derive_deftly(Bytes)]
+struct Unit;
+
+#[derive(Debug, PartialEq, Deftly)]
+#[derive_deftly(Bytes)]
+struct EmptyTuple();
+
+#[derive(Debug, PartialEq, Deftly)]
+#[derive_deftly(Bytes)]
+struct Pair(u8, u16);
+
+#[derive(Debug, PartialEq, Deftly)]
+#[derive_deftly(Bytes)]
+struct Wrapper<T>(T, Vec<T>);
+
+#[derive(Debug, PartialEq, Deftly)]
+#[derive_deftly(Bytes)]
+struct Bounded<T, U>(T, Option<U>)
+where
+    T: Copy,
+    U: Clone;
+
+#[derive(Debug, PartialEq, Deftly)]
+#[derive_deftly(Bytes)]
+struct Named<T>
+where
+    T: Clone,
+{
+    value: T,
+    items: Vec<T>,
+}
+
+#[derive(Debug, PartialEq, Deftly)]
+#[derive_deftly(Bytes)]
+enum Shape<T> {
+    Empty,
+    Point(T, T),
+    Labeled { label: String, value: T },
+}
+
+#[derive(Debug, PartialEq, Deftly)]
+#[derive_deftly(Bytes)]
+#[deftly(bytes(tagged))]
+struct Record {
+    id: u32,
+    name: String,
+}
+
+// `Record` as written by a newer release, which added a field:
+#[derive(Debug, PartialEq, Deftly)]
+#[derive_deftly(Bytes)]
+#[deftly(bytes(tagged))]
+struct RecordWithScore {
+    id: u32,
+    name: String,
+    score: u16,
+}
+
+// `Record` as written by a newer version, which added a field that older data falls back on:
+#[derive(Debug, PartialEq, Deftly)]
+#[derive_deftly(Bytes)]
+#[deftly(bytes(tagged, version = "1"))]
+struct RecordV1 {
+    id: u32,
+    name: String,
+    #[deftly(bytes(default = "1"))]
+    weight: u8,
+}
+
+#[test]
+fn unit_struct() {
+    assert_eq!(Unit::FIXED_SIZE, Some(0));
+    assert_roundtrip!(Unit);
+    assert!(Unit::from_bytes(&[0]).is_err());
+}
+
+#[test]
+fn tuple_struct() {
+    assert_eq!(EmptyTuple::FIXED_SIZE, Some(0));
+    assert_eq!(Pair::FIXED_SIZE, Some(3));
+    assert_eq!(&*Pair(1, 0x0302).to_bytes(), [1, 2, 3]);
+
+    assert_roundtrip!(EmptyTuple());
+    assert_roundtrip!(Pair(1, 0x0302));
+}
+
+#[test]
+fn generic_tuple_struct() {
+    assert_eq!(<Wrapper<u8>>::FIXED_SIZE, None);
+
+    assert_roundtrip!(Wrapper(1u8, vec![2, 3]));
+    assert_roundtrip!(Wrapper(String::from("a"), vec![String::from("b")]));
+}
+
+#[test]
+fn where_clauses() {
+    assert_eq!(<Bounded<u32, u8>>::FIXED_SIZE, None);
+
+    assert_roundtrip!(Bounded(7u32, Some(String::from("value"))));
+    assert_roundtrip!(Bounded::<u32, u8>(7, None));
+    assert_roundtrip!(Named {
+        value: 1u16,
+        items: vec![2, 3, 4],
+    });
+}
+
+#[test]
+fn generic_enum() {
+    assert_roundtrip!(Shape::<i32>::Empty);
+    assert_roundtrip!(Shape::Point(-1i32, 1));
+    assert_roundtrip!(Shape::Labeled {
+        label: String::from("origin"),
+        value: 0i32,
+    });
+}
+
+#[test]
+fn error_path() {
+    let bytes = [2, 1, 0, 0, 0, 0, 0, 0, 0];
+    let err = <Shape<i32>>::from_bytes(&bytes).unwrap_err();
+    assert_eq!(err.path(), [("Shape::Labeled", "label")]);
+}
+
+#[test]
+fn tagged_roundtrip() {
+    assert_roundtrip!(Record {
+        id: 1,
+        name: String::from("a"),
+    });
+    assert_roundtrip!(RecordV1 {
+        id: 1,
+        name: String::from("a"),
+        weight: 2,
+    });
+}
+
+#[test]
+fn tagged_unknown_field() {
+    let newer = RecordWithScore {
+        id: 1,
+        name: String::from("a"),
+        score: 2,
+    };
+    let older = Record {
+        id: 1,
+        name: String::from("a"),
+    };
+    assert_eq!(Record::from_bytes(&newer.to_bytes()).unwrap(), older);
+    assert_eq!(Record::read_from(&*newer.to_bytes()).unwrap(), older);
+}
+
+#[test]
+fn tagged_missing_field() {
+    let older = Record {
+        id: 1,
+        name: String::from("a"),
+    };
+    let err = RecordWithScore::from_bytes(&older.to_bytes()).unwrap_err();
+    assert!(matches!(err, BytesError::InField { .. }));
+    assert_eq!(err.path(), [("RecordWithScore", "score")]);
+
+    let newer = RecordV1::from_bytes(&older.to_bytes()).unwrap();
+    assert_eq!(newer.weight, 1);
+}
+
+#[test]
+fn tagged_version() {
+    let newer = RecordV1 {
+        id: 1,
+        name: String::from("a"),
+        weight: 2,
+    };
+    assert!(matches!(
+        Record::from_bytes(&newer.to_bytes()),
+        Err(BytesError::UnsupportedVersion(1))
+    ));
+}
+
+#[test]
+fn tagged_duplicate_field() {
+    let mut bytes = Vec::new();
+    0u8.write_into(&mut bytes);
+    2u64.write_into(&mut bytes);
+    for id in [1u32, 2] {
+        0u16.write_into(&mut bytes);
+        4usize.write_into(&mut bytes);
+        id.write_into(&mut bytes);
+    }
+    assert!(matches!(
+        Record::from_bytes(&bytes),
+        Err(BytesError::DuplicateField(_))
+    ));
+}
@@ -0,0 +1,33 @@
+use bytes::{Bytes, BytesError, Packed, assert_roundtrip};
+
+#[test]
+fn roundtrip() {
+    let digits = Packed::<_, 4>::new(vec![1u8, 9, 0, 5, 7]).unwrap();
+    assert_eq!(digits.required_size(), 8 + 3);
+    assert_roundtrip!(digits);
+
+    assert_roundtrip!(Packed::<_, 1>::new([true, false, true]).unwrap());
+    assert_roundtrip!(Packed::<_, 12>::new(Box::<[u16]>::from([0xfff, 0, 0x123])).unwrap());
+}
+
+#[test]
+fn value_too_wide() {
+    assert!(matches!(
+        Packed::<_, 4>::new(vec![1u8, 16, 2]),
+        Err(BytesError::ValueTooWide { index: 1, bits: 4 })
+    ));
+    assert!(Packed::<_, 64>::new([u64::MAX]).is_ok());
+}
+
+#[test]
+fn non_zero_padding() {
+    let mut bytes = Packed::<_, 4>::new([1u8, 2, 3])
+        .unwrap()
+        .to_bytes()
+        .into_vec();
+    *bytes.last_mut().unwrap() |= 0xf0;
+    assert!(matches!(
+        Packed::<[u8; 3], 4>::from_bytes(&bytes),
+        Err(BytesError::NonZeroPadding(1))
+    ));
+}
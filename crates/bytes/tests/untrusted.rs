@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+
+use bytes::{Bytes, BytesError, BytesReader, BytesRef, ReadLimits};
+
+// A length prefix of `u64::MAX`, which overflows when added to any position:
+const HUGE_LEN: [u8; 8] = [0xff; 8];
+
+#[test]
+fn borrowed_len_overflow() {
+    assert!(matches!(
+        <&[u8]>::from_bytes_ref(&HUGE_LEN),
+        Err(BytesError::EndOfData { .. })
+    ));
+    assert!(matches!(
+        <&str>::from_bytes_ref(&HUGE_LEN),
+        Err(BytesError::EndOfData { .. })
+    ));
+    assert!(matches!(
+        String::from_bytes(&HUGE_LEN),
+        Err(BytesError::EndOfData { .. })
+    ));
+}
+
+#[test]
+fn huge_collection_len() {
+    // A length prefix of 2^60 elements, with none of them present:
+    let bytes = [0, 0, 0, 0, 0, 0, 0, 0x10];
+    assert!(matches!(
+        <Vec<Vec<u8>>>::from_bytes(&bytes),
+        Err(BytesError::EndOfData { .. })
+    ));
+    assert!(matches!(
+        <Vec<String>>::read_from(&bytes[..]),
+        Err(BytesError::EndOfData { .. })
+    ));
+    assert!(matches!(
+        <HashSet<Vec<u8>>>::from_bytes(&bytes),
+        Err(BytesError::EndOfData { .. })
+    ));
+    assert!(matches!(
+        <Vec<&str>>::from_bytes_ref(&bytes),
+        Err(BytesError::EndOfData { .. })
+    ));
+}
+
+#[test]
+fn sub_reader_len_overflow() {
+    let bytes = [0; 16];
+    let mut reader = BytesReader::new(&bytes);
+    u64::read(&mut reader).unwrap();
+    assert!(matches!(
+        reader.sub_reader(usize::MAX, u64::read),
+        Err(BytesError::EndOfData { .. })
+    ));
+    assert_eq!(reader.position(), 8);
+}
+
+#[test]
+fn sub_reader_trailing_data() {
+    let bytes = [0; 16];
+    let mut reader = BytesReader::new(&bytes);
+    assert!(matches!(
+        reader.sub_reader(12, u64::read),
+        Err(BytesError::TrailingData(8))
+    ));
+
+    let mut reader = BytesReader::new(&bytes);
+    assert_eq!(reader.sub_reader(8, u64::read).unwrap(), 0);
+    assert_eq!(reader.position(), 8);
+}
+
+#[test]
+fn sub_reader_allocations() {
+    // Two records of 8 bytes each, which only exceed the limit together:
+    let record = vec![0u8; 8];
+    let mut bytes = Vec::new();
+    for _ in 0..2 {
+        bytes.extend_from_slice(&record.required_size().to_bytes());
+        bytes.extend_from_slice(&record.to_bytes());
+    }
+
+    let limits = ReadLimits {
+        max_alloc: 12,
+        ..Default::default()
+    };
+    let mut reader = BytesReader::with_limits(&bytes, limits);
+    let len = usize::read(&mut reader).unwrap();
+    assert_eq!(reader.sub_reader(len, <Vec<u8>>::read).unwrap(), record);
+    let len = usize::read(&mut reader).unwrap();
+    assert!(matches!(
+        reader.sub_reader(len, <Vec<u8>>::read),
+        Err(BytesError::LimitExceeded(_))
+    ));
+}
+
+#[test]
+fn huge_streamed_len() {
+    // A length prefix of 2^56 bytes, with none of them present:
+    let bytes = [0, 0, 0, 0, 0, 0, 0, 1];
+    assert!(matches!(
+        String::read_from(&bytes[..]),
+        Err(BytesError::EndOfData { .. })
+    ));
+    assert!(matches!(
+        <Vec<u8>>::read_from(&bytes[..]),
+        Err(BytesError::EndOfData { .. })
+    ));
+}
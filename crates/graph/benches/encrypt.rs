@@ -0,0 +1,48 @@
+use std::hint::black_box;
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use graph::{ColorDomain, CommitmentContext, Graph, GraphBuilder};
+use sha2::{Digest, Sha256};
+
+// As many nodes as the graph of a 9x9 sudoku, which can't be used here without a circular
+// dependency. Only the nodes are hashed, so the edges don't matter.
+const LEN: usize = 90;
+
+fn encrypt(c: &mut Criterion) {
+    let mut builder = GraphBuilder::new();
+    builder.add_nodes((0..LEN).map(|i| i as u8 % (ColorDomain::SUDOKU.colors() + 1)));
+    let graph: Graph<u8> = builder.build().expect("graph has no edges");
+
+    let context = CommitmentContext {
+        session: [0; 16],
+        round: 0,
+    };
+
+    let mut group = c.benchmark_group("Graph<u8>");
+    group.bench_function("encrypt", |b| {
+        b.iter(|| black_box(&graph).encrypt(&context))
+    });
+    group.bench_function("encrypt_par", |b| {
+        b.iter(|| black_box(&graph).encrypt_par(&context))
+    });
+    group.finish();
+}
+
+// The backends on their own, over as many bytes as a commitment hashes:
+fn hash(c: &mut Criterion) {
+    let input = [0x5a; 24 + 16 + 8 + 32 + 1];
+
+    let mut group = c.benchmark_group("commitment hash");
+    group.throughput(Throughput::Bytes(input.len() as u64));
+    group.bench_function("sha256", |b| {
+        b.iter(|| <[u8; 32]>::from(Sha256::digest(black_box(input))))
+    });
+    #[cfg(feature = "blake3")]
+    group.bench_function("blake3", |b| {
+        b.iter(|| <[u8; 32]>::from(blake3::hash(black_box(&input))))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, encrypt, hash);
+criterion_main!(benches);
@@ -0,0 +1,65 @@
+use crate::{Graph, NodeIndex};
+
+// The neighbors of every node of a graph, stored contiguously by node, so that querying them
+// doesn't scan every edge. It is a snapshot of the edges at the time it was built, and has to be
+// rebuilt if they change.
+#[derive(Clone, Debug)]
+pub struct Adjacency {
+    offsets: Box<[usize]>,
+    neighbors: Box<[usize]>,
+}
+
+impl Adjacency {
+    pub fn new<T, I>(graph: &Graph<T, I>) -> Self
+    where
+        I: NodeIndex,
+    {
+        let len = graph.nodes.len();
+
+        // Counts the neighbors of each node, and turns the counts into the offsets where they end:
+        let mut offsets = vec![0; len + 1];
+        for edge in &graph.edges {
+            let (a, b) = edge.indices();
+            offsets[a + 1] += 1;
+            if b != a {
+                offsets[b + 1] += 1;
+            }
+        }
+        for i in 0..len {
+            offsets[i + 1] += offsets[i];
+        }
+
+        let mut filled = offsets.clone();
+        let mut neighbors = vec![0; offsets[len]];
+        for edge in &graph.edges {
+            let (a, b) = edge.indices();
+            neighbors[filled[a]] = b;
+            filled[a] += 1;
+            if b != a {
+                neighbors[filled[b]] = a;
+                filled[b] += 1;
+            }
+        }
+
+        Self {
+            offsets: offsets.into(),
+            neighbors: neighbors.into(),
+        }
+    }
+
+    pub fn neighbors(&self, node: usize) -> &[usize] {
+        &self.neighbors[self.offsets[node]..self.offsets[node + 1]]
+    }
+
+    pub fn degree(&self, node: usize) -> usize {
+        self.offsets[node + 1] - self.offsets[node]
+    }
+
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -0,0 +1,88 @@
+use std::{collections::HashSet, ops::Range};
+
+use crate::{Edge, Graph, GraphError, NodeIndex};
+
+// Builds a graph incrementally. Edges are normalized to point from the lower node to the higher
+// one, and duplicates are dropped, so that constraints can be added from every node's point of
+// view without counting them up front.
+#[derive(Clone, Debug)]
+pub struct GraphBuilder<T, I = u32> {
+    nodes: Vec<T>,
+    edges: Vec<Edge<I>>,
+    seen: HashSet<Edge<I>>,
+}
+
+impl<T, I> GraphBuilder<T, I>
+where
+    I: NodeIndex,
+{
+    pub fn new() -> Self {
+        Self::with_capacity(0, 0)
+    }
+
+    pub fn with_capacity(nodes: usize, edges: usize) -> Self {
+        Self {
+            nodes: Vec::with_capacity(nodes),
+            edges: Vec::with_capacity(edges),
+            seen: HashSet::with_capacity(edges),
+        }
+    }
+
+    pub fn add_node(&mut self, value: T) -> usize {
+        self.nodes.push(value);
+        self.nodes.len() - 1
+    }
+
+    pub fn add_nodes<V>(&mut self, values: V) -> Range<usize>
+    where
+        V: IntoIterator<Item = T>,
+    {
+        let start = self.nodes.len();
+        self.nodes.extend(values);
+        start..self.nodes.len()
+    }
+
+    // Returns whether the edge was new. Edges may refer to nodes that haven't been added yet,
+    // which is checked when the graph is built.
+    pub fn add_edge(&mut self, a: usize, b: usize) -> Result<bool, GraphError> {
+        if a == b {
+            return Err(GraphError::SelfLoop { node: a });
+        }
+
+        let (a, b) = (a.min(b), a.max(b));
+        let edge = Edge::new(a, b).ok_or(GraphError::IndexOverflow { node: b })?;
+        let added = self.seen.insert(edge);
+        if added {
+            self.edges.push(edge);
+        }
+
+        Ok(added)
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn build(self) -> Result<Graph<T, I>, GraphError> {
+        let graph = Graph {
+            nodes: self.nodes.into(),
+            edges: self.edges.into(),
+        };
+        graph.validate()?;
+
+        Ok(graph)
+    }
+}
+
+impl<T, I> Default for GraphBuilder<T, I>
+where
+    I: NodeIndex,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
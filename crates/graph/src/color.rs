@@ -0,0 +1,32 @@
+// The colors a graph is colored with, numbered from 1 up to `colors`, with 0 reserved for nodes
+// that are uncolored. Committing to a coloring only reveals that it is proper if the colors are
+// shuffled every round, with a `Permutation` of the domain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ColorDomain {
+    colors: u8,
+}
+
+impl ColorDomain {
+    pub const UNCOLORED: u8 = 0;
+
+    pub const THREE_COLORING: Self = Self::new(3);
+    pub const SUDOKU: Self = Self::new(9);
+    pub const SUDOKU_16: Self = Self::new(16);
+
+    pub const fn new(colors: u8) -> Self {
+        Self { colors }
+    }
+
+    // The smallest domain containing every color of the nodes:
+    pub fn of(nodes: &[u8]) -> Self {
+        Self::new(nodes.iter().copied().max().unwrap_or(Self::UNCOLORED))
+    }
+
+    pub fn colors(&self) -> u8 {
+        self.colors
+    }
+
+    pub fn contains(&self, color: u8) -> bool {
+        color <= self.colors
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::HashSet;
+
+use crate::{Adjacency, Graph, GraphError, NodeIndex};
+
+// A proper coloring of a graph, with colors numbered from 1 like those of a `ColorDomain`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Coloring {
+    pub colors: Box<[u8]>,
+    pub count: u8,
+}
+
+impl<T, I> Graph<T, I>
+where
+    I: NodeIndex,
+{
+    // Colors the nodes from the most to the least connected, each with the smallest color none of
+    // its neighbors have. The values of the nodes are ignored.
+    pub fn greedy_coloring(&self) -> Result<Coloring, GraphError> {
+        let adjacency = self.colorable_adjacency()?;
+        let mut order: Vec<_> = (0..adjacency.len()).collect();
+        order.sort_by_key(|&node| std::cmp::Reverse(adjacency.degree(node)));
+
+        Coloring::new(greedy(&adjacency, &order))
+    }
+
+    // Colors the nodes with the DSATUR heuristic: the next node to color is the one whose neighbors
+    // have the most distinct colors, and then the most connected one, which usually uses fewer
+    // colors than `greedy_coloring`. The values of the nodes are ignored.
+    pub fn dsatur_coloring(&self) -> Result<Coloring, GraphError> {
+        let adjacency = self.colorable_adjacency()?;
+        let len = adjacency.len();
+
+        let mut colors: Vec<Option<usize>> = vec![None; len];
+        let mut neighbor_colors = vec![HashSet::new(); len];
+        for _ in 0..len {
+            let node = (0..len)
+                .filter(|&node| colors[node].is_none())
+                .max_by_key(|&node| (neighbor_colors[node].len(), adjacency.degree(node)))
+                .expect("an uncolored node remains");
+
+            let color = (0..)
+                .find(|color| !neighbor_colors[node].contains(color))
+                .expect("a new color is always free");
+            colors[node] = Some(color);
+            for &neighbor in adjacency.neighbors(node) {
+                neighbor_colors[neighbor].insert(color);
+            }
+        }
+
+        Coloring::new(colors.into_iter().flatten().collect())
+    }
+
+    fn colorable_adjacency(&self) -> Result<Adjacency, GraphError> {
+        match self.edges.iter().find(|edge| edge.0 == edge.1) {
+            Some(edge) => Err(GraphError::SelfLoop {
+                node: edge.0.index(),
+            }),
+            None => Ok(self.adjacency()),
+        }
+    }
+}
+
+impl Coloring {
+    // Shifts colors counted from 0 to be counted from 1.
+    fn new(colors: Vec<usize>) -> Result<Self, GraphError> {
+        let count = colors.iter().max().map_or(0, |&max| max + 1);
+        let count = u8::try_from(count).map_err(|_| GraphError::TooManyColors { count })?;
+
+        Ok(Self {
+            colors: colors.into_iter().map(|color| color as u8 + 1).collect(),
+            count,
+        })
+    }
+}
+
+// Colors the nodes in order, each with the smallest color none of its neighbors have, counting
+// from 0. Self-loops are ignored, since no coloring can satisfy them.
+pub(crate) fn greedy(adjacency: &Adjacency, order: &[usize]) -> Vec<usize> {
+    let mut colors: Vec<Option<usize>> = vec![None; adjacency.len()];
+    let mut taken = Vec::new();
+    let mut count = 0;
+    for &node in order {
+        taken.clear();
+        taken.resize(count + 1, false);
+        for &neighbor in adjacency.neighbors(node) {
+            if let Some(color) = colors[neighbor].filter(|_| neighbor != node) {
+                taken[color] = true;
+            }
+        }
+
+        let color = taken
+            .iter()
+            .position(|&taken| !taken)
+            .expect("a new color is always free");
+        colors[node] = Some(color);
+        count = count.max(color + 1);
+    }
+
+    colors.into_iter().flatten().collect()
+}
@@ -0,0 +1,57 @@
+use std::fmt::Write;
+
+use crate::{Edge, Graph, NodeIndex};
+
+impl<T, I> Graph<T, I>
+where
+    I: NodeIndex,
+{
+    // Renders the graph in the Graphviz DOT language, labeling each node with `labeler`.
+    pub fn to_dot<F>(&self, labeler: F) -> String
+    where
+        F: Fn(usize, &T) -> String,
+    {
+        self.to_dot_highlighted(labeler, &[])
+    }
+
+    // Like `to_dot`, but draws the given edges in bold red, such as the ones a verifier challenged.
+    pub fn to_dot_highlighted<F>(&self, labeler: F, highlighted: &[Edge<I>]) -> String
+    where
+        F: Fn(usize, &T) -> String,
+    {
+        let mut dot = String::from("graph {\n");
+        for (i, node) in self.nodes.iter().enumerate() {
+            let label = escape(&labeler(i, node));
+            writeln!(dot, "    n{i} [label=\"{label}\"];")
+                .expect("writing to a string cannot fail");
+        }
+        for edge in &self.edges {
+            let is_highlighted = highlighted.contains(edge);
+            let (a, b) = edge.indices();
+            let attrs = if is_highlighted {
+                " [color=red, penwidth=2]"
+            } else {
+                ""
+            };
+            writeln!(dot, "    n{a} -- n{b}{attrs};").expect("writing to a string cannot fail");
+        }
+        dot.push_str("}\n");
+
+        dot
+    }
+}
+
+fn escape(label: &str) -> String {
+    let mut escaped = String::with_capacity(label.len());
+    for c in label.chars() {
+        match c {
+            '"' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
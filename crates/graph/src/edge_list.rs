@@ -0,0 +1,66 @@
+use std::{fmt::Display, fmt::Write, str::FromStr};
+
+use crate::{Edge, Graph, GraphError, NodeIndex};
+
+impl<T, I> Graph<T, I>
+where
+    I: NodeIndex,
+{
+    // Renders the graph as plain text: the values of the nodes on the first line, and then the
+    // nodes of one edge per line, all separated by whitespace.
+    pub fn to_edge_list(&self) -> String
+    where
+        T: Display,
+    {
+        let mut list = String::new();
+        for (i, node) in self.nodes.iter().enumerate() {
+            let separator = if i == 0 { "" } else { " " };
+            write!(list, "{separator}{node}").expect("writing to a string cannot fail");
+        }
+        list.push('\n');
+        for edge in &self.edges {
+            let (a, b) = edge.indices();
+            writeln!(list, "{a} {b}").expect("writing to a string cannot fail");
+        }
+
+        list
+    }
+
+    // Parses the output of `to_edge_list`. Empty lines, and lines starting with `#`, are skipped.
+    pub fn from_edge_list(list: &str) -> Result<Self, GraphError>
+    where
+        T: FromStr,
+    {
+        let mut lines = list
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+        let nodes = match lines.next() {
+            Some((line, values)) => values
+                .split_whitespace()
+                .map(|value| value.parse())
+                .collect::<Result<_, _>>()
+                .map_err(|_| GraphError::InvalidEdgeList { line })?,
+            None => Box::default(),
+        };
+
+        let edges = lines
+            .map(|(line, nodes)| {
+                let mut nodes = nodes.split_whitespace().map(str::parse);
+                match (nodes.next(), nodes.next(), nodes.next()) {
+                    (Some(Ok(a)), Some(Ok(b)), None) => {
+                        Edge::new(a, b).ok_or(GraphError::IndexOverflow { node: a.max(b) })
+                    }
+                    _ => Err(GraphError::InvalidEdgeList { line }),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        let graph = Self { nodes, edges };
+        graph.validate()?;
+
+        Ok(graph)
+    }
+}
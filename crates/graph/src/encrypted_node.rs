@@ -0,0 +1,61 @@
+use std::fmt::{self, Debug, Display, Formatter};
+
+use derive_deftly::Deftly;
+
+use bytes::{
+    BytesError, HexBytes, derive_deftly_template_Bytes, derive_deftly_template_Describe,
+    derive_deftly_template_FixedBytes,
+};
+
+// A commitment to the color of a node, as made by `hash`. It is displayed in hex, and compared in
+// constant time, so that comparing a guess against it doesn't reveal how close the guess was.
+#[derive(Clone, Copy, Default, Deftly)]
+#[derive_deftly(Bytes, Describe, FixedBytes)]
+pub struct EncryptedNode(pub [u8; 32]);
+
+impl EncryptedNode {
+    pub fn from_hex(hex: &str) -> Result<Self, BytesError> {
+        hex.parse().map(|HexBytes(bytes)| Self(bytes))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl PartialEq for EncryptedNode {
+    fn eq(&self, other: &Self) -> bool {
+        let diff = self
+            .0
+            .iter()
+            .zip(&other.0)
+            .fold(0, |diff, (a, b)| diff | (a ^ b));
+        std::hint::black_box(diff) == 0
+    }
+}
+
+impl Eq for EncryptedNode {}
+
+impl Display for EncryptedNode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(&HexBytes(self.0), f)
+    }
+}
+
+impl Debug for EncryptedNode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "EncryptedNode({self})")
+    }
+}
+
+impl From<[u8; 32]> for EncryptedNode {
+    fn from(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<EncryptedNode> for [u8; 32] {
+    fn from(node: EncryptedNode) -> Self {
+        node.0
+    }
+}
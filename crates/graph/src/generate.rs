@@ -0,0 +1,59 @@
+// Random graphs, for exercising the protocol on more than the graphs of puzzles. Both generators
+// panic if `p` is not a probability, or if the nodes don't fit the index type.
+
+use rand::Rng;
+
+use crate::{ColorDomain, Graph, GraphBuilder, NodeIndex};
+
+// A graph where each pair of distinct nodes is connected with probability `p`, with every node
+// set to the default value.
+pub fn erdos_renyi<T, I, R>(nodes: usize, p: f64, rng: &mut R) -> Graph<T, I>
+where
+    T: Default,
+    I: NodeIndex,
+    R: Rng + ?Sized,
+{
+    let mut builder = GraphBuilder::with_capacity(nodes, 0);
+    builder.add_nodes((0..nodes).map(|_| T::default()));
+    for a in 0..nodes {
+        for b in a + 1..nodes {
+            if rng.random_bool(p) {
+                builder.add_edge(a, b).expect("nodes fit the index type");
+            }
+        }
+    }
+    builder.build().expect("nodes fit the index type")
+}
+
+// A graph that is colorable with the colors of `domain`, along with a coloring of it. Each node is
+// given a random color, and each pair of nodes of different colors is connected with probability
+// `p`. The nodes of the graph are uncolored. Panics if the domain has no colors.
+pub fn planted<I, R>(
+    nodes: usize,
+    domain: ColorDomain,
+    p: f64,
+    rng: &mut R,
+) -> (Graph<u8, I>, Box<[u8]>)
+where
+    I: NodeIndex,
+    R: Rng + ?Sized,
+{
+    assert!(domain.colors() > 0, "domain has no colors");
+
+    let coloring: Box<[u8]> = (0..nodes)
+        .map(|_| rng.random_range(1..=domain.colors()))
+        .collect();
+
+    let mut builder = GraphBuilder::with_capacity(nodes, 0);
+    builder.add_nodes((0..nodes).map(|_| ColorDomain::UNCOLORED));
+    for a in 0..nodes {
+        for b in a + 1..nodes {
+            if coloring[a] != coloring[b] && rng.random_bool(p) {
+                builder.add_edge(a, b).expect("nodes fit the index type");
+            }
+        }
+    }
+    let graph = builder.build().expect("nodes fit the index type");
+
+    (graph, coloring)
+}
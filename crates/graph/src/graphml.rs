@@ -0,0 +1,52 @@
+use std::fmt::{Display, Write};
+
+use crate::{Graph, NodeIndex};
+
+impl<T, I> Graph<T, I>
+where
+    I: NodeIndex,
+{
+    // Renders the graph as an undirected GraphML document, with the value of each node as its
+    // `value` attribute.
+    pub fn to_graphml(&self) -> String
+    where
+        T: Display,
+    {
+        let mut graphml = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"value\" for=\"node\" attr.name=\"value\" attr.type=\"string\"/>\n",
+            "  <graph id=\"G\" edgedefault=\"undirected\">\n",
+        ));
+        for (i, node) in self.nodes.iter().enumerate() {
+            let value = escape(&node.to_string());
+            writeln!(
+                graphml,
+                "    <node id=\"n{i}\"><data key=\"value\">{value}</data></node>"
+            )
+            .expect("writing to a string cannot fail");
+        }
+        for edge in &self.edges {
+            let (a, b) = edge.indices();
+            writeln!(graphml, "    <edge source=\"n{a}\" target=\"n{b}\"/>")
+                .expect("writing to a string cannot fail");
+        }
+        graphml.push_str("  </graph>\n</graphml>\n");
+
+        graphml
+    }
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,31 @@
+use std::{fmt::Debug, hash::Hash};
+
+use bytes::FixedBytes;
+
+// The integer type identifying the nodes of a graph. Graphs default to `u32`, but small ones can
+// use `u16` to halve the size of their edges, in memory as well as encoded.
+pub trait NodeIndex: Copy + Debug + Ord + Hash + Send + Sync + FixedBytes {
+    // Returns `None` if the index doesn't fit.
+    fn from_usize(index: usize) -> Option<Self>;
+    fn index(self) -> usize;
+}
+
+macro_rules! impl_node_index {
+    ($($ty:ty),*) => {
+        $(
+            impl NodeIndex for $ty {
+                #[inline]
+                fn from_usize(index: usize) -> Option<Self> {
+                    index.try_into().ok()
+                }
+
+                #[inline]
+                fn index(self) -> usize {
+                    self as usize
+                }
+            }
+        )*
+    };
+}
+
+impl_node_index!(u8, u16, u32, usize);
@@ -0,0 +1,23 @@
+use crate::{ColorDomain, Edge, Graph, GraphBuilder, GraphError, NodeIndex};
+
+// A problem that reduces to coloring a graph, such as a puzzle, which can then be proven solved
+// without revealing the solution. Constraints are given as pairs of nodes that must be colored
+// differently, in any order and with duplicates, and `to_graph` builds the graph from them.
+pub trait IntoColoringInstance {
+    // The colors of the nodes, where 0 is uncolored:
+    fn nodes(&self) -> impl Iterator<Item = u8>;
+    fn constraints(&self) -> impl Iterator<Item = Edge<usize>>;
+    fn domain(&self) -> ColorDomain;
+
+    fn to_graph<I>(&self) -> Result<Graph<u8, I>, GraphError>
+    where
+        I: NodeIndex,
+    {
+        let mut builder = GraphBuilder::new();
+        builder.add_nodes(self.nodes());
+        for Edge(a, b) in self.constraints() {
+            builder.add_edge(a, b)?;
+        }
+        builder.build()
+    }
+}
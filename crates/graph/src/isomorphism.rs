@@ -0,0 +1,74 @@
+use crate::{Edge, Graph, NodeIndex};
+
+impl<T, I> Graph<T, I>
+where
+    I: NodeIndex,
+{
+    // Moves node `i` to `node_perm[i]`, along with its value and edges. Panics if `node_perm` is
+    // not a permutation of the nodes.
+    pub fn relabel(&self, node_perm: &[usize]) -> Self
+    where
+        T: Clone,
+    {
+        assert!(
+            is_permutation(node_perm, self.nodes.len()),
+            "not a permutation of the nodes"
+        );
+
+        let mut nodes = self.nodes.to_vec();
+        for (node, &to) in self.nodes.iter().zip(node_perm) {
+            nodes[to] = node.clone();
+        }
+        let edges = self
+            .edges
+            .iter()
+            .map(|edge| {
+                let (a, b) = edge.indices();
+                Edge::new(node_perm[a], node_perm[b]).expect("permuted nodes fit the index type")
+            })
+            .collect();
+
+        Self {
+            nodes: nodes.into(),
+            edges,
+        }
+    }
+
+    // Whether moving node `i` of this graph to `node_perm[i]` gives the edges of `other`, in any
+    // order and direction. The values of the nodes are not compared.
+    pub fn is_isomorphic_under<U>(&self, other: &Graph<U, I>, node_perm: &[usize]) -> bool {
+        if self.nodes.len() != other.nodes.len()
+            || self.edges.len() != other.edges.len()
+            || !is_permutation(node_perm, self.nodes.len())
+        {
+            return false;
+        }
+
+        let normalized = |(a, b): (usize, usize)| (a.min(b), a.max(b));
+        let mut permuted: Vec<_> = self
+            .edges
+            .iter()
+            .map(|edge| {
+                let (a, b) = edge.indices();
+                normalized((node_perm[a], node_perm[b]))
+            })
+            .collect();
+        let mut edges: Vec<_> = other
+            .edges
+            .iter()
+            .map(|edge| normalized(edge.indices()))
+            .collect();
+        permuted.sort_unstable();
+        edges.sort_unstable();
+
+        permuted == edges
+    }
+}
+
+fn is_permutation(node_perm: &[usize], len: usize) -> bool {
+    let mut seen = vec![false; len];
+    node_perm.len() == len
+        && node_perm
+            .iter()
+            .all(|&node| node < len && !std::mem::replace(&mut seen[node], true))
+}
@@ -0,0 +1,68 @@
+use derive_deftly::Deftly;
+
+use bytes::{derive_deftly_template_Bytes, derive_deftly_template_Describe};
+
+use crate::{Edge, Graph, GraphError, NodeIndex};
+
+// A graph with a label for every node, such as where the node came from in the problem that was
+// reduced to the graph, for reporting nodes in terms of that problem.
+#[derive(Clone, Debug)]
+pub struct Labeled<T, L, I: NodeIndex = u32> {
+    graph: Graph<T, I>,
+    labels: Box<[L]>,
+}
+
+impl<T, L, I> Labeled<T, L, I>
+where
+    I: NodeIndex,
+{
+    pub fn new(graph: Graph<T, I>, labels: Box<[L]>) -> Result<Self, GraphError> {
+        if labels.len() != graph.nodes.len() {
+            return Err(GraphError::LabelCountMismatch {
+                nodes: graph.nodes.len(),
+                labels: labels.len(),
+            });
+        }
+
+        Ok(Self { graph, labels })
+    }
+
+    pub fn graph(&self) -> &Graph<T, I> {
+        &self.graph
+    }
+
+    pub fn labels(&self) -> &[L] {
+        &self.labels
+    }
+
+    pub fn into_parts(self) -> (Graph<T, I>, Box<[L]>) {
+        (self.graph, self.labels)
+    }
+
+    pub fn label(&self, node: usize) -> &L {
+        &self.labels[node]
+    }
+
+    pub fn edge_labels(&self, edge: Edge<I>) -> (&L, &L) {
+        let (a, b) = edge.indices();
+        (&self.labels[a], &self.labels[b])
+    }
+
+    // Like `Graph::to_dot_highlighted`, but labels the nodes with `labeler` given their labels.
+    pub fn to_dot_highlighted<F>(&self, labeler: F, highlighted: &[Edge<I>]) -> String
+    where
+        F: Fn(&L, &T) -> String,
+    {
+        self.graph
+            .to_dot_highlighted(|i, node| labeler(&self.labels[i], node), highlighted)
+    }
+}
+
+// An edge with a label, such as the kind of constraint it comes from, so that challenges can be
+// weighted by kind and violations reported in terms of the problem.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deftly)]
+#[derive_deftly(Bytes, Describe)]
+pub struct LabeledEdge<L, I: NodeIndex = u32> {
+    pub edge: Edge<I>,
+    pub label: L,
+}
@@ -1,67 +1,307 @@
-use std::ops::Index;
+use std::{
+    cmp::Ordering,
+    error::Error,
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+    ops::Index,
+    slice,
+};
 
 use derive_deftly::Deftly;
 use rand::Rng;
+#[cfg(not(feature = "blake3"))]
 use sha2::{Digest, Sha256};
 
-use bytes::derive_deftly_template_Bytes;
+use bytes::{
+    Envelope, derive_deftly_template_Bytes, derive_deftly_template_Describe,
+    derive_deftly_template_FixedBytes,
+};
 
-pub fn hash(value: u8, key: u64) -> EncryptedNode {
+pub use adjacency::Adjacency;
+pub use builder::GraphBuilder;
+pub use color::ColorDomain;
+pub use coloring::Coloring;
+pub use encrypted_node::EncryptedNode;
+pub use index::NodeIndex;
+pub use instance::IntoColoringInstance;
+pub use labeled::{Labeled, LabeledEdge};
+pub use merkle::{MerkleHash, MerkleProof, MerkleTree, verify_merkle_opening};
+pub use opening::Opening;
+pub use permutation::Permutation;
+pub use round::Round;
+pub use stats::GraphStats;
+
+mod adjacency;
+mod builder;
+mod color;
+mod coloring;
+mod dot;
+mod edge_list;
+mod encrypted_node;
+pub mod generate;
+mod graphml;
+mod index;
+mod instance;
+mod isomorphism;
+mod labeled;
+mod merkle;
+mod opening;
+#[cfg(feature = "rayon")]
+mod par;
+mod permutation;
+#[cfg(feature = "petgraph")]
+mod petgraph_interop;
+mod round;
+mod stats;
+
+pub const PROTOCOL_MAGIC: u32 = u32::from_be_bytes(*b"ZKPS");
+// Commitments made with BLAKE3 can't be checked with SHA-256, so the versions tell them apart:
+pub const PROTOCOL_VERSION: u8 = if cfg!(feature = "blake3") { 6 } else { 5 };
+
+pub type Message<T> = Envelope<T, PROTOCOL_MAGIC, PROTOCOL_VERSION>;
+
+// Prefixed to everything that is committed to, so that commitments can't be mistaken for hashes
+// made for other purposes:
+const COMMITMENT_TAG: &[u8] = b"zero-trust/commitment/v1";
+
+pub const SALT_SIZE: usize = 32;
+
+pub type Salt = [u8; SALT_SIZE];
+
+pub type SessionId = [u8; 16];
+
+// Where a commitment was made. It is hashed along with the color, so that a commitment from one
+// session or round can't be replayed in another.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deftly)]
+#[derive_deftly(Bytes, Describe, FixedBytes)]
+pub struct CommitmentContext {
+    pub session: SessionId,
+    pub round: u64,
+}
+
+// Commits to a color. The salt is as wide as the hash, so the color can't be recovered by trying
+// every salt, and every commitment gets a fresh one, so equal colors don't commit equally. The
+// hash is SHA-256, or BLAKE3 if the `blake3` feature is enabled.
+pub fn hash(value: u8, salt: &Salt, context: &CommitmentContext) -> EncryptedNode {
+    #[cfg(not(feature = "blake3"))]
     let mut hasher = Sha256::new();
-    hasher.update((value as u64 ^ key).to_le_bytes());
-    let output = hasher.finalize();
-    output.as_slice().try_into().expect("size is not 32 bytes")
+    #[cfg(feature = "blake3")]
+    let mut hasher = blake3::Hasher::new();
+
+    hasher.update(COMMITMENT_TAG);
+    hasher.update(context.session.as_slice());
+    hasher.update(context.round.to_le_bytes().as_slice());
+    hasher.update(salt);
+    hasher.update([value].as_slice());
+
+    EncryptedNode(hasher.finalize().into())
 }
 
-#[derive(Clone, Debug, Deftly)]
-#[derive_deftly(Bytes)]
-pub struct Graph<T> {
+// Graphs are equal if their nodes and edges are, in the same order. Canonicalizing them first
+// makes the order of the edges irrelevant.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deftly)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive_deftly(Bytes, Describe)]
+#[deftly(bytes(validate = "Self::validate"))]
+pub struct Graph<T, I: NodeIndex = u32> {
     pub nodes: Box<[T]>,
-    pub edges: Box<[Edge]>,
+    pub edges: Box<[Edge<I>]>,
 }
 
-pub type EncryptedNode = [u8; 32];
+impl<T, I> Graph<T, I>
+where
+    I: NodeIndex,
+{
+    // Checks that every edge connects nodes of the graph. Decoding does this automatically, so that
+    // malformed graphs from the wire are rejected before indexing into them can panic.
+    pub fn validate(&self) -> Result<(), GraphError> {
+        let len = self.nodes.len();
+        match self
+            .edges
+            .iter()
+            .map(|edge| edge.indices())
+            .find(|&(a, b)| a >= len || b >= len)
+        {
+            Some((a, b)) => Err(GraphError::EdgeOutOfBounds {
+                edge: Edge(a, b),
+                len,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    // Scans every edge, so prefer `adjacency` when querying many nodes:
+    pub fn neighbors(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.edges.iter().filter_map(move |edge| {
+            let (a, b) = edge.indices();
+            if a == node {
+                Some(b)
+            } else if b == node {
+                Some(a)
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn degree(&self, node: usize) -> usize {
+        self.neighbors(node).count()
+    }
 
-impl<T> Graph<T> {
-    pub fn get(&self, edge: Edge) -> (&T, &T) {
-        (&self[edge.0], &self[edge.1])
+    pub fn adjacency(&self) -> Adjacency {
+        Adjacency::new(self)
     }
 
-    pub fn get_copied(&self, edge: Edge) -> (T, T)
+    // Converts the value of every node, keeping the edges as they are.
+    pub fn map_nodes<U, F>(&self, f: F) -> Graph<U, I>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Graph {
+            nodes: self.nodes.iter().map(f).collect(),
+            edges: self.edges.clone(),
+        }
+    }
+
+    // Points every edge from its lower node to its higher one, sorts them, and removes duplicates,
+    // so that graphs with the same edges are equal.
+    pub fn canonicalize(&mut self) -> &mut Self {
+        let mut edges: Vec<_> = self
+            .edges
+            .iter()
+            .map(|&Edge(a, b)| Edge(a.min(b), a.max(b)))
+            .collect();
+        edges.sort_unstable_by_key(|edge| (edge.0, edge.1));
+        edges.dedup();
+        self.edges = edges.into();
+        self
+    }
+
+    // The subgraph of the given nodes and the edges between them, where node `i` is `nodes[i]` of
+    // this graph. Panics if a node is out of bounds or given twice.
+    pub fn induced(&self, nodes: &[usize]) -> Self
+    where
+        T: Clone,
+    {
+        let mut remapped = vec![None; self.nodes.len()];
+        for (i, &node) in nodes.iter().enumerate() {
+            let index = I::from_usize(i).expect("subgraph is no larger than the graph");
+            assert!(
+                remapped[node].replace(index).is_none(),
+                "node {node} is given twice"
+            );
+        }
+
+        Self {
+            nodes: nodes.iter().map(|&node| self[node].clone()).collect(),
+            edges: self
+                .edges
+                .iter()
+                .filter_map(|edge| {
+                    let (a, b) = edge.indices();
+                    Some(Edge(remapped[a]?, remapped[b]?))
+                })
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, edge: Edge<I>) -> (&T, &T) {
+        let (a, b) = edge.indices();
+        (&self[a], &self[b])
+    }
+
+    // Like `get`, but fails instead of panicking if the edge is out of bounds, such as one received
+    // from the other party.
+    pub fn try_get(&self, edge: Edge<I>) -> Result<(&T, &T), GraphError> {
+        let (a, b) = edge.indices();
+        match (self.nodes.get(a), self.nodes.get(b)) {
+            (Some(a), Some(b)) => Ok((a, b)),
+            _ => Err(GraphError::EdgeOutOfBounds {
+                edge: Edge(a, b),
+                len: self.nodes.len(),
+            }),
+        }
+    }
+
+    pub fn get_copied(&self, edge: Edge<I>) -> (T, T)
     where
         T: Copy,
     {
-        (self[edge.0], self[edge.1])
+        let (a, b) = edge.indices();
+        (self[a], self[b])
+    }
+}
+
+impl<T, I> Graph<T, I>
+where
+    T: PartialEq,
+    I: NodeIndex,
+{
+    // Whether every node has a color other than `uncolored`, and no edge connects two nodes of the
+    // same color.
+    pub fn is_properly_colored(&self, uncolored: &T) -> bool {
+        self.nodes.iter().all(|node| node != uncolored)
+            && self.violations(uncolored).next().is_none()
+    }
+
+    // The edges that connect two nodes of the same color. Uncolored nodes don't conflict with
+    // anything, so a partially colored graph only has violations where it is already wrong.
+    pub fn violations<'a>(&'a self, uncolored: &'a T) -> impl Iterator<Item = Edge<I>> + 'a {
+        self.edges.iter().copied().filter(move |&edge| {
+            let (a, b) = self.get(edge);
+            a == b && a != uncolored
+        })
     }
 }
 
-impl Graph<u8> {
-    pub fn map(&mut self, mapper: &[u8; 10]) -> &mut Self {
+impl<I> Graph<u8, I>
+where
+    I: NodeIndex,
+{
+    // Recolors every node through `permutation`, which has to cover every color of the graph.
+    pub fn map(&mut self, permutation: &Permutation) -> &mut Self {
         self.nodes
             .iter_mut()
-            .for_each(|node| *node = mapper[*node as usize]);
+            .for_each(|node| *node = permutation.apply(*node));
         self
     }
 
-    pub fn encrypt(&self) -> (Box<[EncryptedNode]>, Keys) {
-        let mut rng = rand::rng();
+    // Like `map`, but leaves this graph as it is, so that every permutation applies to the original
+    // coloring.
+    pub fn permuted(&self, permutation: &Permutation) -> Self {
+        self.map_nodes(|&node| permutation.apply(node))
+    }
+
+    pub fn encrypt(&self, context: &CommitmentContext) -> (Box<[EncryptedNode]>, Keys) {
+        self.encrypt_with_rng(context, &mut rand::rng())
+    }
+
+    // Like `encrypt`, but draws the salts from `rng`, so that a seeded generator reproduces the
+    // same commitments.
+    pub fn encrypt_with_rng<R>(
+        &self,
+        context: &CommitmentContext,
+        rng: &mut R,
+    ) -> (Box<[EncryptedNode]>, Keys)
+    where
+        R: Rng + ?Sized,
+    {
         let mut encrypted_nodes = Vec::with_capacity(self.nodes.len());
         let mut keys = Vec::with_capacity(self.nodes.len());
-        for (node, (encrypted_node, key)) in self
-            .nodes
-            .iter()
-            .zip(encrypted_nodes.iter_mut().zip(keys.iter_mut()))
-        {
-            let rand_key = rng.random();
-            *encrypted_node = hash(*node, rand_key);
-            *key = rand_key;
+        for node in &self.nodes {
+            let salt: Salt = rng.random();
+            encrypted_nodes.push(hash(*node, &salt, context));
+            keys.push(salt);
         }
 
         (encrypted_nodes.into(), Keys(keys.into()))
     }
 }
 
-impl<T> Index<usize> for Graph<T> {
+impl<T, I> Index<usize> for Graph<T, I>
+where
+    I: NodeIndex,
+{
     type Output = T;
 
     fn index(&self, index: usize) -> &Self::Output {
@@ -69,22 +309,179 @@ impl<T> Index<usize> for Graph<T> {
     }
 }
 
+// Edges are undirected, so they compare, order, and hash the same regardless of the order of their
+// nodes.
 #[derive(Clone, Copy, Debug, Deftly)]
-#[derive_deftly(Bytes)]
-pub struct Edge(pub usize, pub usize);
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive_deftly(Bytes, Describe, FixedBytes)]
+pub struct Edge<I = u32>(pub I, pub I);
+
+impl<I> Edge<I>
+where
+    I: Ord,
+{
+    fn normalized(&self) -> (&I, &I) {
+        (Ord::min(&self.0, &self.1), Ord::max(&self.0, &self.1))
+    }
+}
+
+impl<I> PartialEq for Edge<I>
+where
+    I: Ord,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.normalized() == other.normalized()
+    }
+}
+
+impl<I> Eq for Edge<I> where I: Ord {}
 
-pub struct Keys(Box<[u64]>);
+impl<I> PartialOrd for Edge<I>
+where
+    I: Ord,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<I> Ord for Edge<I>
+where
+    I: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.normalized().cmp(&other.normalized())
+    }
+}
+
+impl<I> Hash for Edge<I>
+where
+    I: Ord + Hash,
+{
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        self.normalized().hash(state);
+    }
+}
+
+impl<I> Edge<I>
+where
+    I: NodeIndex,
+{
+    // Returns `None` if either node doesn't fit the index type.
+    pub fn new(a: usize, b: usize) -> Option<Self> {
+        Some(Self(I::from_usize(a)?, I::from_usize(b)?))
+    }
+
+    pub fn indices(self) -> (usize, usize) {
+        (self.0.index(), self.1.index())
+    }
+}
+
+// The salts opening a commitment to every node. Since they reveal the colors, they are wiped from
+// memory when dropped if the `zeroize` feature is enabled.
+#[derive(Clone, Debug, Deftly)]
+#[derive_deftly(Bytes, Describe)]
+pub struct Keys(Box<[Salt]>);
 
 impl Keys {
-    pub fn get(&self, edge: Edge) -> (u64, u64) {
-        (self[edge.0], self[edge.1])
+    pub fn get<I>(&self, edge: Edge<I>) -> (Salt, Salt)
+    where
+        I: NodeIndex,
+    {
+        let (a, b) = edge.indices();
+        (self[a], self[b])
+    }
+
+    // Like `get`, but fails instead of panicking if the edge is out of bounds.
+    pub fn try_get<I>(&self, edge: Edge<I>) -> Result<(Salt, Salt), GraphError>
+    where
+        I: NodeIndex,
+    {
+        let (a, b) = edge.indices();
+        match (self.0.get(a), self.0.get(b)) {
+            (Some(&a), Some(&b)) => Ok((a, b)),
+            _ => Err(GraphError::EdgeOutOfBounds {
+                edge: Edge(a, b),
+                len: self.len(),
+            }),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, Salt> {
+        self.0.iter()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Keys {
+    fn drop(&mut self) {
+        self.0.iter_mut().for_each(zeroize::Zeroize::zeroize);
+    }
+}
+
+impl<'a> IntoIterator for &'a Keys {
+    type Item = &'a Salt;
+    type IntoIter = slice::Iter<'a, Salt>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
 impl Index<usize> for Keys {
-    type Output = u64;
+    type Output = Salt;
 
     fn index(&self, index: usize) -> &Self::Output {
         &self.0[index]
     }
 }
+
+#[derive(Debug)]
+pub enum GraphError {
+    EdgeOutOfBounds { edge: Edge<usize>, len: usize },
+    SelfLoop { node: usize },
+    IndexOverflow { node: usize },
+    InvalidEdgeList { line: usize },
+    TooManyColors { count: usize },
+    LabelCountMismatch { nodes: usize, labels: usize },
+    InvalidPermutation { color: usize },
+}
+
+impl Error for GraphError {}
+
+impl Display for GraphError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::EdgeOutOfBounds { edge, len } => write!(
+                f,
+                "edge ({}, {}) is out of bounds for a graph of {len} nodes",
+                edge.0, edge.1
+            ),
+            Self::SelfLoop { node } => write!(f, "edge connects node {node} to itself"),
+            Self::IndexOverflow { node } => {
+                write!(f, "node {node} doesn't fit the index type of the graph")
+            }
+            Self::InvalidEdgeList { line } => write!(f, "invalid edge list at line {line}"),
+            Self::TooManyColors { count } => {
+                write!(f, "coloring needs {count} colors, more than fit in a byte")
+            }
+            Self::LabelCountMismatch { nodes, labels } => {
+                write!(f, "graph has {nodes} nodes but {labels} labels")
+            }
+            Self::InvalidPermutation { color } => {
+                write!(f, "permutation maps color {color} to an invalid color")
+            }
+        }
+    }
+}
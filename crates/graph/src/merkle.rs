@@ -0,0 +1,137 @@
+use derive_deftly::Deftly;
+use sha2::{Digest, Sha256};
+
+use bytes::{derive_deftly_template_Bytes, derive_deftly_template_Describe};
+
+use crate::EncryptedNode;
+
+pub type MerkleHash = [u8; 32];
+
+// Prefixes keeping leaves and inner nodes apart, so that an inner node can't be passed off as a
+// leaf:
+const LEAF_PREFIX: u8 = 0;
+const INNER_PREFIX: u8 = 1;
+
+// A binary hash tree over the encrypted nodes of a round, so that a commitment to all of them is
+// a single root, and each can be opened on its own. A level of odd length pairs its last hash with
+// itself.
+#[derive(Clone, Debug)]
+pub struct MerkleTree {
+    levels: Box<[Box<[MerkleHash]>]>,
+}
+
+// The siblings of a leaf from the bottom of the tree up, hashed together with it to reach the root.
+#[derive(Clone, Debug, PartialEq, Eq, Deftly)]
+#[derive_deftly(Bytes, Describe)]
+pub struct MerkleProof(pub Box<[MerkleHash]>);
+
+impl MerkleTree {
+    pub fn new(leaves: &[EncryptedNode]) -> Self {
+        let mut level: Box<[MerkleHash]> = leaves.iter().map(hash_leaf).collect();
+        let mut levels = Vec::new();
+        while level.len() > 1 {
+            let next = level
+                .chunks(2)
+                .map(|pair| hash_inner(&pair[0], pair.last().expect("chunk is not empty")))
+                .collect();
+            levels.push(level);
+            level = next;
+        }
+        levels.push(level);
+
+        Self {
+            levels: levels.into(),
+        }
+    }
+
+    // The root of a tree without leaves is all zeroes.
+    pub fn root(&self) -> MerkleHash {
+        self.levels
+            .last()
+            .and_then(|level| level.first())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Replaces a leaf, and rehashes the path from it to the root. Panics if `index` is out of
+    // bounds.
+    pub fn update(&mut self, index: usize, leaf: &EncryptedNode) {
+        let mut index = index;
+        self.levels[0][index] = hash_leaf(leaf);
+        for depth in 1..self.levels.len() {
+            let below = &self.levels[depth - 1];
+            let left = index & !1;
+            let hash = hash_inner(&below[left], below.get(left + 1).unwrap_or(&below[left]));
+            index /= 2;
+            self.levels[depth][index] = hash;
+        }
+    }
+
+    // Panics if `index` is out of bounds.
+    pub fn proof(&self, index: usize) -> MerkleProof {
+        assert!(index < self.len(), "leaf {index} is out of bounds");
+
+        let mut index = index;
+        let mut siblings = Vec::with_capacity(self.levels.len() - 1);
+        for level in &self.levels[..self.levels.len() - 1] {
+            siblings.push(*level.get(index ^ 1).unwrap_or(&level[index]));
+            index /= 2;
+        }
+
+        MerkleProof(siblings.into())
+    }
+}
+
+// Checks that `leaf` is at `index` of the tree with the given root and `len` leaves. The last
+// leaf of an odd level is paired with itself, so without the length its proof would also open the
+// index past it.
+pub fn verify_merkle_opening(
+    root: &MerkleHash,
+    len: usize,
+    index: usize,
+    leaf: &EncryptedNode,
+    proof: &MerkleProof,
+) -> bool {
+    let depth = len.next_power_of_two().trailing_zeros() as usize;
+    if index >= len || proof.0.len() != depth {
+        return false;
+    }
+
+    let mut index = index;
+    let mut hash = hash_leaf(leaf);
+    for sibling in &proof.0 {
+        hash = if index.is_multiple_of(2) {
+            hash_inner(&hash, sibling)
+        } else {
+            hash_inner(sibling, &hash)
+        };
+        index /= 2;
+    }
+
+    index == 0 && hash == *root
+}
+
+fn hash_leaf(leaf: &EncryptedNode) -> MerkleHash {
+    Sha256::new()
+        .chain_update([LEAF_PREFIX])
+        .chain_update(leaf.as_bytes())
+        .finalize()
+        .into()
+}
+
+fn hash_inner(left: &MerkleHash, right: &MerkleHash) -> MerkleHash {
+    Sha256::new()
+        .chain_update([INNER_PREFIX])
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
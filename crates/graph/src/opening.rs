@@ -0,0 +1,30 @@
+use derive_deftly::Deftly;
+
+use bytes::{
+    derive_deftly_template_Bytes, derive_deftly_template_Describe,
+    derive_deftly_template_FixedBytes,
+};
+
+use crate::{CommitmentContext, EncryptedNode, Salt, hash};
+
+// What reveals the color committed to by an `EncryptedNode`: the color itself, and the salt it was
+// hashed with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deftly)]
+#[derive_deftly(Bytes, Describe, FixedBytes)]
+pub struct Opening {
+    pub value: u8,
+    pub key: Salt,
+}
+
+impl Opening {
+    // The commitment this opens, in the given context:
+    pub fn commitment(&self, context: &CommitmentContext) -> EncryptedNode {
+        hash(self.value, &self.key, context)
+    }
+}
+
+impl EncryptedNode {
+    pub fn verify_opening(&self, opening: &Opening, context: &CommitmentContext) -> bool {
+        *self == opening.commitment(context)
+    }
+}
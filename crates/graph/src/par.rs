@@ -0,0 +1,35 @@
+use rand::Rng;
+use rayon::prelude::*;
+
+use crate::{CommitmentContext, EncryptedNode, Graph, Keys, NodeIndex, Salt, hash};
+
+impl<I> Graph<u8, I>
+where
+    I: NodeIndex,
+{
+    // Like `encrypt`, but hashes the nodes in parallel.
+    pub fn encrypt_par(&self, context: &CommitmentContext) -> (Box<[EncryptedNode]>, Keys) {
+        self.encrypt_par_with_rng(context, &mut rand::rng())
+    }
+
+    // Like `encrypt_with_rng`, but hashes the nodes in parallel. The salts are drawn up front, in
+    // order, so the commitments are the same as those of `encrypt_with_rng` for the same `rng`.
+    pub fn encrypt_par_with_rng<R>(
+        &self,
+        context: &CommitmentContext,
+        rng: &mut R,
+    ) -> (Box<[EncryptedNode]>, Keys)
+    where
+        R: Rng + ?Sized,
+    {
+        let keys: Box<[Salt]> = self.nodes.iter().map(|_| rng.random()).collect();
+        let encrypted_nodes = self
+            .nodes
+            .par_iter()
+            .zip(&keys)
+            .map(|(node, salt)| hash(*node, salt, context))
+            .collect::<Vec<_>>();
+
+        (encrypted_nodes.into(), Keys(keys))
+    }
+}
@@ -0,0 +1,77 @@
+use std::mem;
+
+use derive_deftly::Deftly;
+use rand::{Rng, seq::SliceRandom};
+
+use bytes::{derive_deftly_template_Bytes, derive_deftly_template_Describe};
+
+use crate::{ColorDomain, GraphError};
+
+// A shuffling of the colors of a `ColorDomain`, which always maps the uncolored 0 to itself.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deftly)]
+#[derive_deftly(Bytes, Describe)]
+#[deftly(bytes(validate = "Self::validate"))]
+pub struct Permutation(Box<[u8]>);
+
+impl Permutation {
+    pub fn identity(domain: ColorDomain) -> Self {
+        Self((0..=domain.colors()).collect())
+    }
+
+    pub fn random<R>(domain: ColorDomain, rng: &mut R) -> Self
+    where
+        R: Rng + ?Sized,
+    {
+        let mut permutation = Self::identity(domain);
+        permutation.0[1..].shuffle(rng);
+        permutation
+    }
+
+    // Checks that every color is mapped to a distinct color, and that 0 is kept. Decoding does
+    // this automatically.
+    pub fn validate(&self) -> Result<(), GraphError> {
+        if self.0.is_empty() {
+            return Err(GraphError::InvalidPermutation { color: 0 });
+        }
+
+        let mut seen = vec![false; self.0.len()];
+        for (color, &mapped) in self.0.iter().enumerate() {
+            let is_valid = (color == 0) == (mapped == ColorDomain::UNCOLORED)
+                && seen
+                    .get_mut(mapped as usize)
+                    .is_some_and(|seen| !mem::replace(seen, true));
+            if !is_valid {
+                return Err(GraphError::InvalidPermutation { color });
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn domain(&self) -> ColorDomain {
+        ColorDomain::new((self.0.len() - 1) as u8)
+    }
+
+    // Panics if the value is not in the domain of the permutation.
+    pub fn apply(&self, value: u8) -> u8 {
+        self.0[value as usize]
+    }
+
+    pub fn inverse(&self) -> Self {
+        let mut inverse = vec![0; self.0.len()];
+        for (color, &mapped) in self.0.iter().enumerate() {
+            inverse[mapped as usize] = color as u8;
+        }
+        Self(inverse.into())
+    }
+
+    // The permutation applying `self` first, and then `other`. Panics if their domains differ.
+    pub fn compose(&self, other: &Self) -> Self {
+        assert_eq!(
+            self.0.len(),
+            other.0.len(),
+            "permutations are of different domains"
+        );
+        Self(self.0.iter().map(|&value| other.apply(value)).collect())
+    }
+}
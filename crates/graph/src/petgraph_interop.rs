@@ -0,0 +1,49 @@
+use petgraph::{EdgeType, graph::IndexType};
+
+use crate::{Graph, GraphBuilder, GraphError, NodeIndex};
+
+// The constraints are undirected, but they are added to directed graphs as well, from the lower
+// node to the higher one, for algorithms that only take directed graphs.
+impl<T, I, Ty> From<&Graph<T, I>> for petgraph::Graph<T, (), Ty>
+where
+    T: Clone,
+    I: NodeIndex,
+    Ty: EdgeType,
+{
+    fn from(graph: &Graph<T, I>) -> Self {
+        let mut petgraph = Self::with_capacity(graph.nodes.len(), graph.edges.len());
+        let nodes: Vec<_> = graph
+            .nodes
+            .iter()
+            .map(|node| petgraph.add_node(node.clone()))
+            .collect();
+        for edge in &graph.edges {
+            let (a, b) = edge.indices();
+            petgraph.add_edge(nodes[a.min(b)], nodes[a.max(b)], ());
+        }
+
+        petgraph
+    }
+}
+
+// Edge weights and directions are dropped, and so are parallel edges. Self-loops can't be
+// constraints, so they are rejected.
+impl<T, E, Ty, Ix, I> TryFrom<&petgraph::Graph<T, E, Ty, Ix>> for Graph<T, I>
+where
+    T: Clone,
+    Ty: EdgeType,
+    Ix: IndexType,
+    I: NodeIndex,
+{
+    type Error = GraphError;
+
+    fn try_from(petgraph: &petgraph::Graph<T, E, Ty, Ix>) -> Result<Self, Self::Error> {
+        let mut builder = GraphBuilder::with_capacity(petgraph.node_count(), petgraph.edge_count());
+        builder.add_nodes(petgraph.node_weights().cloned());
+        for edge in petgraph.raw_edges() {
+            builder.add_edge(edge.source().index(), edge.target().index())?;
+        }
+
+        builder.build()
+    }
+}
@@ -0,0 +1,116 @@
+use rand::Rng;
+
+use crate::{
+    ColorDomain, CommitmentContext, EncryptedNode, Graph, Keys, MerkleTree, NodeIndex, Opening,
+    Permutation, SessionId,
+};
+
+// One round of the proof: a commitment to the coloring with its colors permuted, and what is
+// needed to open it. Only the root of the tree over the encrypted nodes has to be published.
+#[derive(Clone, Debug)]
+pub struct Round {
+    pub context: CommitmentContext,
+    pub permutation: Permutation,
+    pub encrypted_nodes: Box<[EncryptedNode]>,
+    pub keys: Keys,
+    pub tree: MerkleTree,
+}
+
+impl Round {
+    // The color a node was committed to in this round:
+    pub fn color<I>(&self, graph: &Graph<u8, I>, node: usize) -> u8
+    where
+        I: NodeIndex,
+    {
+        self.permutation.apply(graph[node])
+    }
+
+    // Reveals the color a node was committed to in this round. Panics if the node is out of
+    // bounds.
+    pub fn open<I>(&self, graph: &Graph<u8, I>, node: usize) -> Opening
+    where
+        I: NodeIndex,
+    {
+        Opening {
+            value: self.color(graph, node),
+            key: self.keys[node],
+        }
+    }
+
+    // Commits to a new color of a node, with a fresh salt, leaving the commitments to the other
+    // nodes as they are. `value` is the color before permuting it. Panics if the node is out of
+    // bounds.
+    pub fn recommit<R>(&mut self, node: usize, value: u8, rng: &mut R)
+    where
+        R: Rng + ?Sized,
+    {
+        let opening = Opening {
+            value: self.permutation.apply(value),
+            key: rng.random(),
+        };
+        let encrypted_node = opening.commitment(&self.context);
+
+        self.encrypted_nodes[node] = encrypted_node;
+        self.keys.0[node] = opening.key;
+        self.tree.update(node, &encrypted_node);
+    }
+}
+
+impl<I> Graph<u8, I>
+where
+    I: NodeIndex,
+{
+    // Commits to the coloring `count` times, each time under a fresh permutation of the colors of
+    // `domain`, so that opening one edge per round reveals nothing about the coloring as a whole.
+    // Each round is committed to in the context of its index in the session, and all randomness
+    // is drawn from `rng`, so that a seeded generator reproduces the same rounds.
+    pub fn commit_rounds<R>(
+        &self,
+        session: SessionId,
+        domain: ColorDomain,
+        count: usize,
+        rng: &mut R,
+    ) -> Vec<Round>
+    where
+        R: Rng + ?Sized,
+    {
+        (0..count)
+            .map(|i| {
+                let context = CommitmentContext {
+                    session,
+                    round: i as u64,
+                };
+                let permutation = Permutation::random(domain, rng);
+                let permuted = self.permuted(&permutation);
+
+                #[cfg(feature = "rayon")]
+                let (encrypted_nodes, keys) = permuted.encrypt_par_with_rng(&context, rng);
+                #[cfg(not(feature = "rayon"))]
+                let (encrypted_nodes, keys) = permuted.encrypt_with_rng(&context, rng);
+
+                let tree = MerkleTree::new(&encrypted_nodes);
+
+                Round {
+                    context,
+                    permutation,
+                    encrypted_nodes,
+                    keys,
+                    tree,
+                }
+            })
+            .collect()
+    }
+
+    // Changes the color of a node, and recommits to it in every round, which is much cheaper than
+    // committing to every round again when only a few nodes change. The roots of the rounds
+    // change, so they have to be published again.
+    pub fn recommit_node<R>(&mut self, rounds: &mut [Round], node: usize, value: u8, rng: &mut R)
+    where
+        R: Rng + ?Sized,
+    {
+        self.nodes[node] = value;
+        for round in rounds {
+            round.recommit(node, value, rng);
+        }
+    }
+}
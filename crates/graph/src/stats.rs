@@ -0,0 +1,87 @@
+use std::collections::HashSet;
+
+use crate::{Adjacency, Graph, NodeIndex, coloring::greedy};
+
+// An overview of the shape of a graph. The bounds are computed greedily, so they are fast but not
+// tight: the chromatic number lies somewhere between them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GraphStats {
+    pub nodes: usize,
+    pub edges: usize,
+    pub min_degree: usize,
+    pub max_degree: usize,
+    // The number of nodes of each degree, indexed by degree:
+    pub degree_histogram: Box<[usize]>,
+    pub clique_lower_bound: usize,
+    pub chromatic_upper_bound: usize,
+}
+
+impl<T, I> Graph<T, I>
+where
+    I: NodeIndex,
+{
+    pub fn stats(&self) -> GraphStats {
+        let adjacency = self.adjacency();
+        let degrees: Vec<_> = (0..adjacency.len())
+            .map(|node| adjacency.degree(node))
+            .collect();
+        let max_degree = degrees.iter().copied().max();
+
+        let mut degree_histogram = vec![0; max_degree.map_or(0, |max| max + 1)];
+        for &degree in &degrees {
+            degree_histogram[degree] += 1;
+        }
+
+        // Nodes with many neighbors are the most constrained, so both bounds consider them first:
+        let mut order: Vec<_> = (0..adjacency.len()).collect();
+        order.sort_by_key(|&node| std::cmp::Reverse(degrees[node]));
+
+        GraphStats {
+            nodes: self.nodes.len(),
+            edges: self.edges.len(),
+            min_degree: degrees.iter().copied().min().unwrap_or(0),
+            max_degree: max_degree.unwrap_or(0),
+            degree_histogram: degree_histogram.into(),
+            clique_lower_bound: self.greedy_clique(&adjacency, &order),
+            chromatic_upper_bound: greedy(&adjacency, &order)
+                .into_iter()
+                .max()
+                .map_or(0, |max| max + 1),
+        }
+    }
+
+    // Grows a clique from every node in turn, adding each neighbor that is connected to all nodes
+    // of the clique so far, and returns the size of the largest one found.
+    fn greedy_clique(&self, adjacency: &Adjacency, order: &[usize]) -> usize {
+        let edges: HashSet<_> = self.edges.iter().map(|edge| edge.indices()).collect();
+        let connected = |a: usize, b: usize| edges.contains(&(a, b)) || edges.contains(&(b, a));
+
+        let mut rank = vec![0; order.len()];
+        for (i, &node) in order.iter().enumerate() {
+            rank[node] = i;
+        }
+
+        let mut largest = usize::from(!order.is_empty());
+        let mut clique = Vec::new();
+        for &node in order {
+            // No clique containing this node can be larger than its neighborhood:
+            if adjacency.degree(node) < largest {
+                break;
+            }
+
+            let mut neighbors = adjacency.neighbors(node).to_vec();
+            neighbors.sort_by_key(|&neighbor| rank[neighbor]);
+
+            clique.clear();
+            clique.push(node);
+            for neighbor in neighbors {
+                if neighbor != node && clique.iter().all(|&member| connected(member, neighbor)) {
+                    clique.push(neighbor);
+                }
+            }
+            largest = largest.max(clique.len());
+        }
+
+        largest
+    }
+}
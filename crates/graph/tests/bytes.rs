@@ -0,0 +1,82 @@
+use bytes::{Bytes, BytesError, Envelope, assert_roundtrip};
+use graph::{
+    CommitmentContext, Edge, EncryptedNode, Graph, LabeledEdge, MerkleProof, MerkleTree, Message,
+    Opening, verify_merkle_opening,
+};
+
+fn triangle() -> Graph<u8> {
+    Graph {
+        nodes: Box::new([1, 2, 3]),
+        edges: Box::new([Edge(0, 1), Edge(1, 2), Edge(2, 0)]),
+    }
+}
+
+#[test]
+fn edge() {
+    let edge: Edge = Edge(0, 1);
+    assert_roundtrip!(edge);
+    assert_roundtrip!(LabeledEdge { edge, label: 7u8 });
+}
+
+#[test]
+fn graph() {
+    assert_roundtrip!(triangle());
+    assert_roundtrip!(Graph::<u8> {
+        nodes: Box::new([]),
+        edges: Box::new([]),
+    });
+}
+
+#[test]
+fn graph_edge_out_of_bounds() {
+    let graph = Graph::<u8> {
+        nodes: Box::new([1, 2]),
+        edges: Box::new([Edge(0, 2)]),
+    };
+    assert!(matches!(
+        Graph::<u8>::from_bytes(&graph.to_bytes()),
+        Err(BytesError::Invalid { .. })
+    ));
+}
+
+#[test]
+fn commitments() {
+    let context = CommitmentContext {
+        session: [1; 16],
+        round: 2,
+    };
+    let opening = Opening {
+        value: 3,
+        key: [4; 32],
+    };
+    assert_roundtrip!(context);
+    assert_roundtrip!(opening);
+    assert_roundtrip!(opening.commitment(&context));
+    assert_roundtrip!(EncryptedNode([5; 32]));
+}
+
+#[test]
+fn merkle_proof() {
+    let leaves: Vec<_> = (0..5).map(|i| EncryptedNode([i; 32])).collect();
+    let tree = MerkleTree::new(&leaves);
+    let root = tree.root();
+    for (i, leaf) in leaves.iter().enumerate() {
+        let proof = tree.proof(i);
+        assert!(verify_merkle_opening(&root, 5, i, leaf, &proof));
+        assert_roundtrip!(proof);
+    }
+
+    // The last leaf is paired with itself, so its proof would otherwise open the index after it:
+    let proof = tree.proof(4);
+    assert!(!verify_merkle_opening(&root, 5, 5, &leaves[4], &proof));
+    let truncated = MerkleProof(proof.0[..2].into());
+    assert!(!verify_merkle_opening(&root, 5, 4, &leaves[4], &truncated));
+
+    assert_roundtrip!(MerkleProof(Box::new([])));
+}
+
+#[test]
+fn message() {
+    let message: Message<Vec<Edge>> = Envelope(vec![Edge(0, 1), Edge(2, 3)]);
+    assert_roundtrip!(message);
+}
@@ -1,4 +1,5 @@
-use std::{array, cell::LazyCell, io, sync::Arc, sync::RwLock, thread};
+use std::collections::HashSet;
+use std::{env, io, process, sync::Arc, sync::LazyLock, sync::RwLock, thread};
 
 use crossterm::{
     cursor,
@@ -7,27 +8,25 @@ use crossterm::{
     style::{ContentStyle, StyledContent, Stylize},
     terminal::{self, ClearType},
 };
-use rand::prelude::*;
+use rand::Rng;
 use tiny_http::{Method, Response, Server};
 
-use bytes::Bytes;
-use graph::{Edge, Graph};
-use sudoku::{PUZZLE, Sudoku, sudoku};
-
-const SOLUTION: LazyCell<Sudoku> = LazyCell::new(|| {
-    sudoku! {
-        4 5 7 3 9 6 2 1 8;
-        3 2 8 1 5 7 4 9 6;
-        9 6 1 2 8 4 7 5 3;
-        7 8 3 4 1 5 9 6 2;
-        6 1 5 9 2 8 3 7 4;
-        2 9 4 7 6 3 1 8 5;
-        8 4 9 6 7 2 5 3 1;
-        5 7 2 8 3 1 6 4 9;
-        1 3 6 5 4 9 8 2 7;
-    }
+use bytes::{Bytes, Envelope, ReadLimits};
+use graph::{ColorDomain, Edge, Graph, Message, SessionId};
+use sudoku::{Step, Sudoku, SudokuHistory, library, sudoku};
+
+// The puzzle is the one of the library with the id given as the first argument, such as `hard-3`,
+// or otherwise read from the `.sdk` or `.sdm` file given as it, if any.
+static PUZZLE: LazyLock<Sudoku> = LazyLock::new(|| match env::args().nth(1) {
+    Some(id) if let Some(puzzle) = library::get(&id) => puzzle,
+    Some(path) => Sudoku::load(&path).unwrap_or_else(|err| {
+        eprintln!("Could not load {path}: {err}");
+        process::exit(1)
+    }),
+    None => sudoku::PUZZLE.clone(),
 });
-const FAKE_SOLUTION: LazyCell<Sudoku> = LazyCell::new(|| {
+static SOLUTION: LazyLock<Option<Sudoku>> = LazyLock::new(|| PUZZLE.solve());
+static FAKE_SOLUTION: LazyLock<Sudoku> = LazyLock::new(|| {
     sudoku! {
         1 2 3 4 5 6 7 8 9;
         4 5 6 7 8 9 1 2 3;
@@ -48,13 +47,12 @@ fn main() -> io::Result<()> {
 }
 
 fn run_verification_server(progress: Arc<RwLock<Sudoku>>) {
-    let mut verification_keys = Vec::new();
-    let mut mappers = Vec::new();
+    let mut rounds = Vec::new();
 
     let server = Server::http("0.0.0.0:8000").expect("valid connection");
     thread::spawn(move || {
         for mut request in server.incoming_requests() {
-            let mut graph = Graph::from(&*progress.read().expect("poisoned"));
+            let graph = Graph::from(&*progress.read().expect("poisoned"));
             let num_edges = graph.edges.len();
 
             let url = request.url();
@@ -64,9 +62,6 @@ fn run_verification_server(progress: Arc<RwLock<Sudoku>>) {
 
             match (request.method(), path) {
                 (Method::Get, "/nodes") => {
-                    verification_keys.clear();
-                    mappers.clear();
-
                     let count = query
                         .and_then(|q| q.split_once('='))
                         .and_then(|(key, value)| (key == "count").then_some(value))
@@ -76,66 +71,60 @@ fn run_verification_server(progress: Arc<RwLock<Sudoku>>) {
                         .min(num_edges);
 
                     let mut rng = rand::rng();
+                    let session: SessionId = rng.random();
+                    rounds = graph.commit_rounds(session, ColorDomain::SUDOKU, count, &mut rng);
 
-                    let mut encrypted_nodes = Vec::with_capacity(count);
-                    for _ in 0..count {
-                        let mut mapper: [u8; 10] = array::from_fn(|i| i as u8);
-                        mapper[1..].shuffle(&mut rng);
-
-                        let (encrypted_nodes_elem, keys) = graph.map(&mapper).encrypt();
-
-                        encrypted_nodes.push(encrypted_nodes_elem);
-                        verification_keys.push(keys);
-                        mappers.push(mapper);
-                    }
-
-                    let bytes = encrypted_nodes.to_bytes();
-                    let _ = request.respond(Response::from_data(bytes));
+                    let roots: Vec<_> = rounds.iter().map(|round| round.tree.root()).collect();
+                    let message: Message<_> = Envelope((session, roots));
+                    let _ = request.respond(Response::from_data(message.to_bytes()));
                 }
 
                 (Method::Post, "/verify") => 'post_verify: {
-                    if verification_keys.is_empty() {
+                    if rounds.is_empty() {
                         let _ = request.respond(Response::empty(400));
                         break 'post_verify;
                     }
 
-                    let mut edge_bytes = Vec::new();
-                    let Ok(_) = request.as_reader().read_to_end(&mut edge_bytes) else {
-                        let _ = request.respond(Response::empty(400));
-                        break 'post_verify;
+                    let limits = ReadLimits {
+                        max_len: rounds.len(),
+                        ..Default::default()
                     };
-
-                    let Ok(edges) = <Vec<Edge>>::from_bytes(&edge_bytes) else {
+                    let Ok(Envelope(edges)) =
+                        <Message<Vec<Edge>>>::read_from_with_limits(request.as_reader(), limits)
+                    else {
                         let _ = request.respond(Response::empty(400));
                         break 'post_verify;
                     };
 
-                    if edges.len() != verification_keys.len() {
+                    if edges.len() != rounds.len() {
                         let _ = request.respond(Response::empty(400));
                         break 'post_verify;
                     }
 
-                    let mut combined_mapper: [u8; 10] = array::from_fn(|i| i as u8);
-
-                    let verification_data: Vec<_> = edges
+                    // The edges come from the verifier, so each has to be one of the graph. Opening
+                    // any other pair of nodes would reveal more than that their colors differ:
+                    let known: HashSet<Edge> = graph.edges.iter().copied().collect();
+                    let verification_data: Option<Vec<_>> = edges
                         .into_iter()
-                        .zip(verification_keys.iter().zip(&mappers))
-                        .map(|(edge, (key, mapper))| {
-                            let (val_0, val_1) = graph.get_copied(edge);
-                            combined_mapper[1..]
-                                .iter_mut()
-                                .for_each(|v| *v = mapper[*v as usize]);
-
-                            (
-                                combined_mapper[val_0 as usize],
-                                combined_mapper[val_1 as usize],
-                                key.get(edge),
-                            )
+                        .zip(&rounds)
+                        .map(|(edge, round)| {
+                            if !known.contains(&edge) {
+                                return None;
+                            }
+                            let (a, b) = edge.indices();
+                            Some((
+                                (round.open(&graph, a), round.open(&graph, b)),
+                                (round.tree.proof(a), round.tree.proof(b)),
+                            ))
                         })
                         .collect();
+                    let Some(verification_data) = verification_data else {
+                        let _ = request.respond(Response::empty(400));
+                        break 'post_verify;
+                    };
 
-                    let verification_data_bytes = verification_data.to_bytes();
-                    let _ = request.respond(Response::from_data(verification_data_bytes));
+                    let message: Message<_> = Envelope(verification_data);
+                    let _ = request.respond(Response::from_data(message.to_bytes()));
                 }
 
                 _ => {
@@ -154,7 +143,9 @@ where
 
     terminal::enable_raw_mode()?;
 
+    let mut history = SudokuHistory::new(progress);
     let mut position = (0usize, 0usize);
+    let mut message = None;
 
     let mut exit_app = false;
     while !exit_app {
@@ -166,10 +157,24 @@ where
             cursor::MoveTo(0, 0)
         )?;
 
-        let puzzle_str = progress.read().expect("poisoned").to_string();
+        let (puzzle_str, status) = {
+            let progress = history.read();
+            let status = if progress.is_solved() {
+                "Solved"
+            } else if progress.is_complete() {
+                "Complete, but some digits conflict"
+            } else {
+                "Not complete"
+            };
+            (progress.to_string(), status)
+        };
         for line in puzzle_str.lines() {
             queue!(w, style::Print(line), cursor::MoveToNextLine(1))?;
         }
+        queue!(w, style::Print(status), cursor::MoveToNextLine(1))?;
+        if let Some(message) = &message {
+            queue!(w, style::Print(message))?;
+        }
 
         let instr_offs = 2 + 4 * 9;
         queue!(
@@ -191,6 +196,10 @@ where
             "Up, Down, Left, Right - Move cursor",
             "1 through 9           - Enter digit",
             "Space or 0            - Clear digit",
+            "h                     - Show a hint",
+            "l                     - Fill in the next cell by logic",
+            "u                     - Undo",
+            "r                     - Redo",
             "s                     - Solve the puzzle",
             "c                     - Clear all digits",
             "f                     - Enter a fake solution",
@@ -220,10 +229,8 @@ where
                 ..
             })) = event::read()
             {
-                let mut progress = progress.write().expect("poisoned");
-                let can_write = !progress
-                    .given
-                    .contains(&(position.0 as usize, position.1 as usize));
+                let (x, y) = position;
+                message = None;
 
                 match code {
                     KeyCode::Esc => exit_app = true,
@@ -239,24 +246,77 @@ where
                     KeyCode::Down => {
                         position.1 = (position.1 + 1) % 9;
                     }
-                    KeyCode::Char(c @ '0'..='9') if can_write => {
-                        progress.grid[position.1][position.0] = c as u8 - '0' as u8;
+                    KeyCode::Char(c @ '1'..='9') => {
+                        let value = c as u8 - b'0';
+                        if let Err(err) = history.edit(|progress| progress.set(x, y, value)) {
+                            message = Some(format!("Can't enter {value}: {err}"));
+                        }
+                    }
+                    KeyCode::Char(' ' | '0') => {
+                        if let Err(err) = history.edit(|progress| progress.clear(x, y)) {
+                            message = Some(format!("Can't clear the cell: {err}"));
+                        }
+                    }
+                    KeyCode::Char('h') => {
+                        let found = history.read().hint();
+                        message = Some(match found {
+                            Some(found) => {
+                                position = found.cell;
+                                found.to_string()
+                            }
+                            None => "No cell can be deduced from the digits entered".to_string(),
+                        });
+                    }
+                    KeyCode::Char('l') => {
+                        // Solving goes on past the first cell that is filled in, but only the
+                        // steps leading up to it are shown.
+                        let steps = match history.read().solve_logically() {
+                            Ok((_, steps)) => steps,
+                            Err(stuck) => stuck.steps,
+                        };
+                        let eliminations = steps.iter().take_while(|step| step.value.is_none());
+                        let eliminations = eliminations.count();
+                        message = Some(match steps.get(eliminations) {
+                            Some(
+                                step @ Step {
+                                    cell: (x, y),
+                                    value: Some(value),
+                                    ..
+                                },
+                            ) => {
+                                history.edit(|progress| progress.grid[*y][*x] = *value);
+                                position = step.cell;
+                                match eliminations {
+                                    0 => step.to_string(),
+                                    n => format!("{step}, after {n} steps ruling out candidates"),
+                                }
+                            }
+                            _ => "No cell can be filled in by logic from here".to_string(),
+                        });
                     }
-                    KeyCode::Char(' ') if can_write => {
-                        progress.grid[position.1][position.0] = 0;
+                    KeyCode::Char('u') => {
+                        history.undo();
                     }
-                    KeyCode::Char('s') => {
-                        progress.grid = SOLUTION.grid;
+                    KeyCode::Char('r') => {
+                        history.redo();
                     }
+                    KeyCode::Char('s') => match &*SOLUTION {
+                        Some(solution) => history.edit(|progress| progress.grid = solution.grid),
+                        None => message = Some("The puzzle has no solution".to_string()),
+                    },
                     KeyCode::Char('c') => {
-                        progress.grid = PUZZLE.grid;
+                        history.edit(|progress| progress.grid = PUZZLE.grid);
                     }
+                    // The fake solutions overwrite the given cells on purpose, to show that the
+                    // verifier catches them.
                     KeyCode::Char('f') => {
-                        progress.given = PUZZLE.given.clone();
-                        progress.grid = FAKE_SOLUTION.grid;
+                        history.edit(|progress| {
+                            progress.given = PUZZLE.given.clone();
+                            progress.grid = FAKE_SOLUTION.grid;
+                        });
                     }
                     KeyCode::Char('g') => {
-                        *progress = FAKE_SOLUTION.clone();
+                        history.edit(|progress| *progress = FAKE_SOLUTION.clone());
                     }
                     _ => continue,
                 }
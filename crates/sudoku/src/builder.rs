@@ -0,0 +1,116 @@
+use crate::{Cage, Constraint, Sudoku, SudokuError, Unit, Variant};
+
+// Builds a sudoku from its given cells, checking each as it is added, and that no two of them
+// break a rule when built, so that every sudoku it builds can be converted to a graph.
+#[derive(Clone)]
+pub struct SudokuBuilder<const N: usize = 9> {
+    grid: [[u8; N]; N],
+    given: Vec<(usize, usize)>,
+    variant: Variant,
+    cages: Vec<Cage>,
+}
+
+impl<const N: usize> SudokuBuilder<N> {
+    pub fn new() -> Self {
+        Self {
+            grid: [[0; N]; N],
+            given: Vec::new(),
+            variant: Variant::Classic,
+            cages: Vec::new(),
+        }
+    }
+
+    // Gives every filled-in cell of the grid.
+    pub fn from_grid(grid: [[u8; N]; N]) -> Result<Self, SudokuError> {
+        let mut builder = Self::new();
+        for (y, row) in grid.into_iter().enumerate() {
+            for (x, value) in row.into_iter().enumerate() {
+                if value != 0 {
+                    builder.add_given(x, y, value)?;
+                }
+            }
+        }
+
+        Ok(builder)
+    }
+
+    pub fn add_given(&mut self, x: usize, y: usize, value: u8) -> Result<(), SudokuError> {
+        if x >= N || y >= N {
+            return Err(SudokuError::OutOfBounds { cell: (x, y) });
+        }
+        if !(1..=N as u8).contains(&value) {
+            return Err(SudokuError::InvalidValue { value });
+        }
+        if self.grid[y][x] != 0 {
+            return Err(SudokuError::Given { cell: (x, y) });
+        }
+
+        self.grid[y][x] = value;
+        self.given.push((x, y));
+        Ok(())
+    }
+
+    pub fn set_variant(&mut self, variant: Variant) {
+        self.variant = variant;
+    }
+
+    // Adds a cage, whose cells must not be in another cage.
+    pub fn add_cage(&mut self, cage: Cage) -> Result<(), SudokuError> {
+        cage.check::<N>(&self.cages)?;
+        self.cages.push(cage);
+        Ok(())
+    }
+
+    pub fn build(self) -> Result<Sudoku<N>, SudokuError> {
+        let sudoku = Sudoku {
+            grid: self.grid,
+            given: self.given.into(),
+            variant: self.variant,
+            cages: self.cages.into(),
+        };
+
+        let diagonals = if sudoku.variant == Variant::Diagonal {
+            2
+        } else {
+            0
+        };
+        let units = [
+            (Unit::Row, N, Constraint::Row),
+            (Unit::Column, N, Constraint::Column),
+            (Unit::Box, N, Constraint::Box),
+            (Unit::Diagonal, diagonals, Constraint::Diagonal),
+        ];
+        let groups = units.into_iter().flat_map(|(unit, count, constraint)| {
+            (0..count).map(move |index| (unit.cells::<N>(index).collect(), constraint))
+        });
+        let cages = sudoku
+            .cages
+            .iter()
+            .map(|cage| (cage.cells.to_vec(), Constraint::Cage));
+
+        for (cells, constraint) in groups.chain(cages) {
+            let mut seen = [None; 256];
+            for (x, y) in cells {
+                let value = sudoku.grid[y][x] as usize;
+                if value == 0 {
+                    continue;
+                }
+                if let Some(first) = seen[value] {
+                    return Err(SudokuError::Conflict {
+                        cells: (first, (x, y)),
+                        constraint,
+                    });
+                }
+                seen[value] = Some((x, y));
+            }
+        }
+
+        Ok(sudoku)
+    }
+}
+
+impl<const N: usize> Default for SudokuBuilder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::{HashMap, HashSet};
+
+use graph::Edge;
+
+use crate::{Sudoku, SudokuError};
+
+// A group of cells, as in killer sudoku, whose values are distinct and add up to `sum`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Cage {
+    pub cells: Box<[(usize, usize)]>,
+    pub sum: u32,
+}
+
+impl Cage {
+    // Checks that the cells of the cage are distinct cells of a sudoku of size `N`, no more than
+    // there are values, and that none of them are in one of the `others`.
+    pub(crate) fn check<const N: usize>(&self, others: &[Cage]) -> Result<(), SudokuError> {
+        if self.cells.len() > N {
+            return Err(SudokuError::CageTooLarge {
+                cells: self.cells.len(),
+            });
+        }
+
+        let caged: HashSet<_> = others.iter().flat_map(|cage| &cage.cells).collect();
+        let mut seen = HashSet::new();
+        for &(x, y) in &self.cells {
+            if x >= N || y >= N {
+                return Err(SudokuError::OutOfBounds { cell: (x, y) });
+            }
+            if caged.contains(&(x, y)) {
+                return Err(SudokuError::OverlappingCages { cell: (x, y) });
+            }
+            if !seen.insert((x, y)) {
+                return Err(SudokuError::DuplicateCageCell { cell: (x, y) });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Every set of distinct values from 1 to `size` that fills the cage and adds up to its sum, as
+    // bit sets where bit `v` is set for value `v`.
+    pub(crate) fn combinations(&self, size: usize) -> Vec<u32> {
+        fn search(
+            first: u32,
+            last: u32,
+            count: usize,
+            sum: u32,
+            chosen: u32,
+            found: &mut Vec<u32>,
+        ) {
+            if count == 0 {
+                if sum == 0 {
+                    found.push(chosen);
+                }
+                return;
+            }
+            for value in first..=last.min(sum) {
+                search(
+                    value + 1,
+                    last,
+                    count - 1,
+                    sum - value,
+                    chosen | 1 << value,
+                    found,
+                );
+            }
+        }
+
+        let mut found = Vec::new();
+        search(1, size as u32, self.cells.len(), self.sum, 0, &mut found);
+        found
+    }
+
+    // The values that appear in some combination of the cage, as a bit set like those of
+    // `combinations`.
+    pub(crate) fn digits(&self, size: usize) -> u32 {
+        self.combinations(size)
+            .into_iter()
+            .fold(0, |digits, combination| digits | combination)
+    }
+}
+
+impl<const N: usize> Sudoku<N> {
+    // Adds killer sudoku cages, which are checked like those of `SudokuBuilder::add_cage`.
+    pub fn with_cages<C>(mut self, cages: C) -> Result<Self, SudokuError>
+    where
+        C: IntoIterator<Item = Cage>,
+    {
+        let mut all = self.cages.into_vec();
+        for cage in cages {
+            cage.check::<N>(&all)?;
+            all.push(cage);
+        }
+
+        self.cages = all.into();
+        Ok(self)
+    }
+}
+
+// The colors that the nodes of the gadgets are restricted to, by connecting them to the
+// constraint nodes of every other value. Selectors are true or false, the nodes of the OR gadgets
+// may also take the third color, and the other nodes take a value and a spare color.
+const TRUE: u8 = 1;
+const FALSE: u8 = 2;
+const THIRD: u8 = 3;
+
+// The spare color of the nodes that keep a cell from having the value `v`, which differs from
+// both colors of the selectors and from `v`:
+fn spare(value: u8) -> u8 {
+    if value == THIRD { THIRD + 1 } else { THIRD }
+}
+
+// The auxiliary nodes that encode the sums of the cages in the graph of a sudoku, which follow the
+// constraint nodes, and the edges between them and the rest of the graph.
+//
+// The values are permuted in every round of the proof, so a sum can't be checked directly.
+// Instead, the gadget of a cage has a selector node for each set of values that fills the cage and
+// adds up to its sum, and an OR gadget over the selectors forces one of them to be true. A true
+// selector keeps every cell of the cage from having a value outside of its set, through a pair of
+// nodes for each cell and value: one that has the spare color if the selector is true, and one
+// that has it if the cell has the value, and the two are connected. Since the values of a cage
+// are distinct, its cells then have exactly the values of the set.
+//
+// The nodes are colored to match the grid, and left uncolored if any cell of their cage is empty.
+pub(crate) struct CageGadgets {
+    pub(crate) nodes: Vec<u8>,
+    pub(crate) edges: Vec<Edge<usize>>,
+    // The index of the cage of each node:
+    pub(crate) cages: Vec<usize>,
+    first: usize,
+    size: usize,
+}
+
+impl CageGadgets {
+    // Adds a node of the given cage that may only have one of the allowed colors.
+    fn add(&mut self, cage: usize, color: u8, allowed: &[u8]) -> usize {
+        let node = self.first + self.nodes.len();
+        self.nodes.push(color);
+        self.cages.push(cage);
+
+        let anchors = self.first - self.size;
+        let excluded = (1..=self.size as u8).filter(|value| !allowed.contains(value));
+        self.edges
+            .extend(excluded.map(|value| Edge(node, anchors + value as usize - 1)));
+
+        node
+    }
+
+    fn color(&self, node: usize) -> u8 {
+        self.nodes[node - self.first]
+    }
+}
+
+impl<const N: usize> Sudoku<N> {
+    pub(crate) fn cage_gadgets(&self) -> CageGadgets {
+        let mut gadgets = CageGadgets {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            cages: Vec::new(),
+            first: Self::CONSTRAINTS + N,
+            size: N,
+        };
+        for (i, cage) in self.cages.iter().enumerate() {
+            self.add_cage_gadget(&mut gadgets, i, cage);
+        }
+
+        gadgets
+    }
+
+    fn add_cage_gadget(&self, gadgets: &mut CageGadgets, i: usize, cage: &Cage) {
+        let combinations = cage.combinations(N);
+
+        // When no values add up to the sum, the cage can't be filled, which a node that can't have
+        // any color proves. When only one set of values does, connecting the cells to the
+        // constraint nodes of the other values already encodes the sum.
+        if combinations.is_empty() {
+            gadgets.add(i, 0, &[]);
+            return;
+        }
+        if combinations.len() == 1 {
+            return;
+        }
+
+        // Two selector colors and two spare colors are needed, which any sudoku with more than one
+        // set of values for a cage has:
+        debug_assert!(N >= 4);
+
+        let node = |(x, y): (usize, usize)| N * y + x;
+        let cells = cage.cells.iter().map(|&(x, y)| self.grid[y][x]);
+        let filled = cells.clone().all(|value| value != 0);
+        let values = cells.fold(0, |values, value| values | 1 << value);
+        let color = |color: u8| if filled { color } else { 0 };
+
+        let selectors: Vec<_> = combinations
+            .iter()
+            .map(|&combination| {
+                let selected = if values == combination { TRUE } else { FALSE };
+                gadgets.add(i, color(selected), &[TRUE, FALSE])
+            })
+            .collect();
+
+        // The nodes that have the spare color of a value if a selector is true, and those that have
+        // it if a cell has the value, which are shared between the selectors:
+        let mut selected = HashMap::new();
+        let mut excluded = HashMap::new();
+        let digits = cage.digits(N);
+        for (&selector, &combination) in selectors.iter().zip(&combinations) {
+            let selector_color = gadgets.color(selector);
+            for value in (1..=N as u8).filter(|&v| digits & !combination & 1 << v != 0) {
+                for &cell in &cage.cells {
+                    let cell_color = color(self.grid[cell.1][cell.0]);
+
+                    // Nodes that keep a cell from having the color of true selectors would
+                    // conflict with the false ones if shared, so each selector gets its own:
+                    if value == TRUE {
+                        let allowed = [TRUE, THIRD];
+                        let excluder = if cell_color == TRUE { THIRD } else { TRUE };
+                        let excluder = gadgets.add(i, color(excluder), &allowed);
+                        let selector_node = match gadgets.color(excluder) {
+                            THIRD => TRUE,
+                            _ => THIRD,
+                        };
+                        let selector_node = gadgets.add(i, color(selector_node), &allowed);
+                        gadgets.edges.extend([
+                            Edge(excluder, node(cell)),
+                            Edge(selector_node, selector),
+                            Edge(selector_node, excluder),
+                        ]);
+                        continue;
+                    }
+
+                    let spare = spare(value);
+                    let selector_node = *selected.entry((selector, spare)).or_insert_with(|| {
+                        let selected = if selector_color == TRUE { spare } else { TRUE };
+                        let node = gadgets.add(i, color(selected), &[TRUE, spare]);
+                        gadgets.edges.push(Edge(node, selector));
+                        node
+                    });
+                    let excluder = *excluded.entry((cell, value)).or_insert_with(|| {
+                        let excluder = if cell_color == value { spare } else { value };
+                        let excluder = gadgets.add(i, color(excluder), &[value, spare]);
+                        gadgets.edges.push(Edge(excluder, node(cell)));
+                        excluder
+                    });
+                    gadgets.edges.push(Edge(selector_node, excluder));
+                }
+            }
+        }
+
+        // Each OR gadget is a triangle of nodes, two of which are connected to its inputs. Its
+        // output can only be true if an input is, and the last output is forced to be true by
+        // connecting it to the constraint nodes of the other colors.
+        let mut output = selectors[0];
+        for &selector in &selectors[1..] {
+            let (a, b, c) = match (gadgets.color(output), gadgets.color(selector)) {
+                (0, _) | (_, 0) => (0, 0, 0),
+                (FALSE, FALSE) => (TRUE, THIRD, FALSE),
+                (FALSE, _) => (THIRD, FALSE, TRUE),
+                _ => (FALSE, THIRD, TRUE),
+            };
+            let allowed = [TRUE, FALSE, THIRD];
+            let a = gadgets.add(i, a, &allowed);
+            let b = gadgets.add(i, b, &allowed);
+            let c = gadgets.add(i, c, &allowed);
+            gadgets.edges.extend([
+                Edge(a, output),
+                Edge(b, selector),
+                Edge(a, b),
+                Edge(a, c),
+                Edge(b, c),
+            ]);
+            output = c;
+        }
+
+        let anchors = Self::CONSTRAINTS - 1;
+        gadgets.edges.extend([
+            Edge(output, anchors + FALSE as usize),
+            Edge(output, anchors + THIRD as usize),
+        ]);
+    }
+}
@@ -0,0 +1,98 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use graph::{Graph, NodeIndex};
+
+use crate::{Sudoku, Variant, units::positions};
+
+impl<const N: usize> Sudoku<N> {
+    // Reads the grid back from a coloring of the graph of a sudoku. The colors don't have to be
+    // the values themselves, such as after permuting them, since the constraint nodes anchor each
+    // color to a value: a cell has value `v` if it has the color of the constraint node of `v`.
+    // Uncolored cells are left empty. The variant and cages of the sudoku are not recovered.
+    pub fn from_coloring<I>(
+        graph: &Graph<u8, I>,
+        given: &[(usize, usize)],
+    ) -> Result<Self, FromColoringError>
+    where
+        I: NodeIndex,
+    {
+        // Any nodes after the constraint nodes encode cages, which aren't recovered:
+        if graph.nodes.len() < Self::CONSTRAINTS + N {
+            return Err(FromColoringError::WrongNodeCount {
+                expected: Self::CONSTRAINTS + N,
+                found: graph.nodes.len(),
+            });
+        }
+
+        // The value of each color, where 0 means the color is not anchored to any value:
+        let mut values = [0; 256];
+        for (value, &color) in (1..=N as u8).zip(&graph.nodes[Self::CONSTRAINTS..]) {
+            if color == 0 {
+                return Err(FromColoringError::UncoloredConstraint { value });
+            }
+            if values[color as usize] != 0 {
+                return Err(FromColoringError::SharedColor {
+                    values: (values[color as usize], value),
+                });
+            }
+            values[color as usize] = value;
+        }
+
+        let mut grid = [[0; N]; N];
+        for ((x, y), &color) in positions::<N>().zip(&graph.nodes) {
+            grid[y][x] = match (color, values[color as usize]) {
+                (0, _) => 0,
+                (_, 0) => {
+                    return Err(FromColoringError::UnanchoredColor {
+                        cell: (x, y),
+                        color,
+                    });
+                }
+                (_, value) => value,
+            };
+        }
+
+        Ok(Self {
+            grid,
+            given: given.into(),
+            variant: Variant::Classic,
+            cages: Box::new([]),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum FromColoringError {
+    WrongNodeCount { expected: usize, found: usize },
+    UncoloredConstraint { value: u8 },
+    SharedColor { values: (u8, u8) },
+    UnanchoredColor { cell: (usize, usize), color: u8 },
+}
+
+impl Error for FromColoringError {}
+
+impl Display for FromColoringError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::WrongNodeCount { expected, found } => {
+                write!(f, "expected at least {expected} nodes but found {found}")
+            }
+            Self::UncoloredConstraint { value } => {
+                write!(f, "constraint node of {value} is uncolored")
+            }
+            Self::SharedColor { values: (a, b) } => {
+                write!(f, "constraint nodes of {a} and {b} have the same color")
+            }
+            Self::UnanchoredColor {
+                cell: (x, y),
+                color,
+            } => write!(
+                f,
+                "cell ({x}, {y}) has color {color}, which no constraint node has"
+            ),
+        }
+    }
+}
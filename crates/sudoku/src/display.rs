@@ -0,0 +1,94 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{Sudoku, symbol};
+
+impl<const N: usize> Sudoku<N> {
+    // Draws a horizontal border, given the characters for the ends, the crossings with the
+    // borders between boxes and between cells, and the line itself.
+    fn write_border(
+        f: &mut Formatter,
+        [left, box_crossing, cell_crossing, right]: [char; 4],
+        line: char,
+    ) -> fmt::Result {
+        for x in 0..N {
+            let crossing = match x {
+                0 => left,
+                _ if x % Self::BOX == 0 => box_crossing,
+                _ => cell_crossing,
+            };
+            write!(f, "{crossing}{line}{line}{line}")?;
+        }
+        write!(f, "{right}")
+    }
+
+    // Given cells are highlighted with terminal escape codes if `styled`, and otherwise put in
+    // brackets.
+    fn write_grid(&self, f: &mut Formatter, styled: bool) -> fmt::Result {
+        Self::write_border(f, ['╔', '╦', '╤', '╗'], '═')?;
+        writeln!(f)?;
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 && y % Self::BOX == 0 {
+                Self::write_border(f, ['╠', '╬', '╪', '╣'], '═')?;
+                writeln!(f)?;
+            } else if y > 0 {
+                Self::write_border(f, ['╟', '╫', '┼', '╢'], '─')?;
+                writeln!(f)?;
+            }
+
+            write!(f, "║")?;
+            for ((x, _), cell) in row {
+                if x > 0 && x % Self::BOX == 0 {
+                    write!(f, "║")?;
+                } else if x > 0 {
+                    write!(f, "│")?
+                }
+
+                match cell {
+                    0 => write!(f, "   ")?,
+                    value => {
+                        let value = symbol(value);
+                        if !self.given.contains(&(x, y)) {
+                            write!(f, " {value} ")?
+                        } else if styled {
+                            write!(f, "\x1b[1;7m {value} \x1b[0m")?
+                        } else {
+                            write!(f, "[{value}]")?
+                        }
+                    }
+                }
+            }
+            writeln!(f, "║")?
+        }
+        Self::write_border(f, ['╚', '╩', '╧', '╝'], '═')
+    }
+
+    // Displays the sudoku like `Display`, but without terminal escape codes, for logs and piped
+    // output.
+    pub fn display_plain(&self) -> PlainDisplay<'_, N> {
+        PlainDisplay(self)
+    }
+
+    // Every cell on a single line, row by row, with `.` for empty cells, as parsed by `FromStr`.
+    pub fn to_compact(&self) -> String {
+        self.cells()
+            .map(|(_, value)| match value {
+                0 => '.',
+                _ => symbol(value),
+            })
+            .collect()
+    }
+}
+
+impl<const N: usize> Display for Sudoku<N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.write_grid(f, true)
+    }
+}
+
+pub struct PlainDisplay<'a, const N: usize>(&'a Sudoku<N>);
+
+impl<const N: usize> Display for PlainDisplay<'_, N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.write_grid(f, false)
+    }
+}
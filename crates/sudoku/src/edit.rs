@@ -0,0 +1,114 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use crate::{Constraint, Sudoku, Variant};
+
+impl<const N: usize> Sudoku<N> {
+    // A classic sudoku where every filled-in cell is given, with values from 1 to `N`.
+    pub fn from_grid(grid: [[u8; N]; N]) -> Result<Self, SudokuError> {
+        if let Some(&value) = grid.iter().flatten().find(|&&value| value as usize > N) {
+            return Err(SudokuError::InvalidValue { value });
+        }
+
+        let mut sudoku = Self {
+            grid,
+            given: Box::new([]),
+            variant: Variant::Classic,
+            cages: Box::new([]),
+        };
+        sudoku.given = sudoku
+            .cells()
+            .filter(|&(_, value)| value != 0)
+            .map(|(cell, _)| cell)
+            .collect();
+
+        Ok(sudoku)
+    }
+
+    // Enters a value from 1 to `N` in a cell that isn't given.
+    pub fn set(&mut self, x: usize, y: usize, value: u8) -> Result<(), SudokuError> {
+        if !(1..=N as u8).contains(&value) {
+            return Err(SudokuError::InvalidValue { value });
+        }
+
+        *self.cell_mut(x, y)? = value;
+        Ok(())
+    }
+
+    // Empties a cell that isn't given.
+    pub fn clear(&mut self, x: usize, y: usize) -> Result<(), SudokuError> {
+        *self.cell_mut(x, y)? = 0;
+        Ok(())
+    }
+
+    fn cell_mut(&mut self, x: usize, y: usize) -> Result<&mut u8, SudokuError> {
+        if x >= N || y >= N {
+            return Err(SudokuError::OutOfBounds { cell: (x, y) });
+        }
+        if self.given.contains(&(x, y)) {
+            return Err(SudokuError::Given { cell: (x, y) });
+        }
+
+        Ok(&mut self.grid[y][x])
+    }
+}
+
+#[derive(Debug)]
+pub enum SudokuError {
+    OutOfBounds {
+        cell: (usize, usize),
+    },
+    InvalidValue {
+        value: u8,
+    },
+    Given {
+        cell: (usize, usize),
+    },
+    OverlappingCages {
+        cell: (usize, usize),
+    },
+    DuplicateCageCell {
+        cell: (usize, usize),
+    },
+    // A cage can't have more cells than there are distinct values.
+    CageTooLarge {
+        cells: usize,
+    },
+    Conflict {
+        cells: ((usize, usize), (usize, usize)),
+        constraint: Constraint,
+    },
+}
+
+impl Error for SudokuError {}
+
+impl Display for SudokuError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { cell: (x, y) } => write!(f, "cell ({x}, {y}) is out of bounds"),
+            Self::InvalidValue { value } => write!(f, "{value} is not a valid value"),
+            Self::Given { cell: (x, y) } => write!(f, "cell ({x}, {y}) is given"),
+            Self::OverlappingCages { cell: (x, y) } => {
+                write!(f, "cell ({x}, {y}) is in more than one cage")
+            }
+            Self::DuplicateCageCell { cell: (x, y) } => {
+                write!(f, "cell ({x}, {y}) is in a cage more than once")
+            }
+            Self::CageTooLarge { cells } => {
+                write!(
+                    f,
+                    "cage of {cells} cells has more cells than there are values"
+                )
+            }
+            Self::Conflict {
+                cells: ((a_x, a_y), (b_x, b_y)),
+                constraint,
+            } => write!(
+                f,
+                "cells ({a_x}, {a_y}) and ({b_x}, {b_y}) have the same value in a {constraint}"
+            ),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{Sudoku, Unit, symbol};
+
+// A cell that can be filled in by logic alone, and the technique that shows it:
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Hint {
+    pub cell: (usize, usize),
+    pub value: u8,
+    pub technique: Technique,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Technique {
+    // The value is the only one that the cell can take.
+    NakedSingle,
+    // The cell is the only one in the unit that can take the value.
+    HiddenSingle(Unit),
+    // The cells of the first unit that can take a value are all in the second unit, so no other
+    // cell of the second unit can take it.
+    LockedCandidates(Unit, Unit),
+    // Two cells of the unit can only take the same two values, so no other cell of the unit can
+    // take them.
+    NakedPair(Unit),
+}
+
+impl<const N: usize> Sudoku<N> {
+    // Finds a cell that can be deduced from the filled-in cells with a single, the same way as the
+    // first step of `solve_logically` would. Returns `None` if no single applies, or if the
+    // filled-in cells break a rule.
+    pub fn hint(&self) -> Option<Hint> {
+        let marks = self.marks(&[[0; N]; N])?;
+        let step = self.next_step(&marks)?;
+
+        Some(Hint {
+            cell: step.cell,
+            value: step.value?,
+            technique: step.technique,
+        })
+    }
+}
+
+impl Display for Hint {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let Self {
+            cell: (x, y),
+            value,
+            technique,
+        } = self;
+        write!(f, "Cell ({x}, {y}) must be {}: ", symbol(*value))?;
+        match technique {
+            Technique::NakedSingle => write!(f, "it is the only digit left for the cell"),
+            Technique::HiddenSingle(unit) => {
+                write!(f, "it is the only cell left for the digit in its {unit}")
+            }
+            technique => write!(f, "it follows from {technique}"),
+        }
+    }
+}
+
+impl Display for Technique {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::NakedSingle => write!(f, "a naked single"),
+            Self::HiddenSingle(unit) => write!(f, "a hidden single in a {unit}"),
+            Self::LockedCandidates(within, along) => {
+                write!(f, "locked candidates in a {within} and a {along}")
+            }
+            Self::NakedPair(unit) => write!(f, "a naked pair in a {unit}"),
+        }
+    }
+}
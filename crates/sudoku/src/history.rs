@@ -0,0 +1,114 @@
+use std::sync::{Arc, RwLock, RwLockReadGuard};
+
+use crate::{Sudoku, units::positions};
+
+// Wraps a sudoku that is shared between threads, recording every edit made through it so that it
+// can be undone and redone. Edits made to the sudoku directly are not recorded.
+pub struct SudokuHistory<const N: usize = 9> {
+    sudoku: Arc<RwLock<Sudoku<N>>>,
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+}
+
+type Cell = (usize, usize);
+
+// The cells that an edit changed, and the given cells if they changed:
+struct Edit {
+    cells: Box<[(Cell, Change<u8>)]>,
+    given: Option<Change<Box<[Cell]>>>,
+}
+
+struct Change<T> {
+    before: T,
+    after: T,
+}
+
+impl<const N: usize> SudokuHistory<N> {
+    pub fn new(sudoku: Arc<RwLock<Sudoku<N>>>) -> Self {
+        Self {
+            sudoku,
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+
+    pub fn read(&self) -> RwLockReadGuard<'_, Sudoku<N>> {
+        self.sudoku.read().expect("poisoned")
+    }
+
+    // Applies `f` to the sudoku as a single edit, which forgets the edits that were undone, and
+    // returns what it returns. Edits that change nothing are not recorded.
+    pub fn edit<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut Sudoku<N>) -> R,
+    {
+        let mut sudoku = self.sudoku.write().expect("poisoned");
+        let (grid, given) = (sudoku.grid, sudoku.given.clone());
+        let result = f(&mut sudoku);
+
+        let cells: Box<[_]> = positions::<N>()
+            .filter(|&(x, y)| grid[y][x] != sudoku.grid[y][x])
+            .map(|(x, y)| {
+                let change = Change {
+                    before: grid[y][x],
+                    after: sudoku.grid[y][x],
+                };
+                ((x, y), change)
+            })
+            .collect();
+        let given = (given != sudoku.given).then(|| Change {
+            before: given,
+            after: sudoku.given.clone(),
+        });
+        if !cells.is_empty() || given.is_some() {
+            self.undo.push(Edit { cells, given });
+            self.redo.clear();
+        }
+
+        result
+    }
+
+    // Returns whether there was an edit to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(edit) = self.undo.pop() else {
+            return false;
+        };
+
+        let mut sudoku = self.sudoku.write().expect("poisoned");
+        for &((x, y), Change { before, .. }) in &edit.cells {
+            sudoku.grid[y][x] = before;
+        }
+        if let Some(given) = &edit.given {
+            sudoku.given = given.before.clone();
+        }
+
+        self.redo.push(edit);
+        true
+    }
+
+    // Returns whether there was an edit to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(edit) = self.redo.pop() else {
+            return false;
+        };
+
+        let mut sudoku = self.sudoku.write().expect("poisoned");
+        for &((x, y), Change { after, .. }) in &edit.cells {
+            sudoku.grid[y][x] = after;
+        }
+        if let Some(given) = &edit.given {
+            sudoku.given = given.after.clone();
+        }
+
+        self.undo.push(edit);
+        true
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
@@ -1,29 +1,72 @@
-use graph::{Edge, Graph};
-use std::cell::LazyCell;
+use bytes::{derive_deftly_template_Bytes, derive_deftly_template_Describe};
+use derive_deftly::Deftly;
+use graph::{ColorDomain, Edge, Graph, IntoColoringInstance, Labeled, LabeledEdge};
+use std::collections::HashSet;
 use std::fmt::{self, Display, Formatter};
+use std::sync::LazyLock;
+use units::positions;
 
+pub use builder::SudokuBuilder;
+pub use cage::Cage;
+pub use coloring::FromColoringError;
+pub use display::PlainDisplay;
+pub use edit::SudokuError;
+pub use hint::{Hint, Technique};
+pub use history::SudokuHistory;
+pub use logic::{Step, Stuck};
+pub use parse::ParseSudokuError;
+#[doc(hidden)]
+pub use parse::{cell_count, grid_from_str};
+pub use units::Unit;
+
+mod builder;
+mod cage;
+mod coloring;
+mod display;
+mod edit;
+mod hint;
+mod history;
+pub mod library;
+mod logic;
+mod parse;
+mod sadman;
+mod shuffle;
+mod solve;
+mod units;
+
+// Builds a sudoku with `SudokuBuilder` from rows of values, where `_` is an empty cell, each
+// ended by `;`, or from a string literal in the format of `FromStr`, such as
+// `"4...962.8 / 3.81...9. / ..."`, which is checked at compile time. Panics if the sudoku is
+// invalid.
 #[macro_export]
 macro_rules! sudoku {
-    (@impl [$($cells:tt)*] [$($rows:tt)*] [$($given:tt)*] ($x:expr, $y:expr) _ $($rest:tt)+ ) => {
-        sudoku!(@impl [$($cells)* 0,] [$($rows)*] [$($given)*] ($x + 1, $y) $($rest)+ )
+    ($puzzle:literal) => {{
+        const N: usize = $crate::cell_count($puzzle).isqrt();
+        const GRID: [[u8; N]; N] = $crate::grid_from_str::<N>($puzzle);
+        $crate::SudokuBuilder::from_grid(GRID)
+            .and_then($crate::SudokuBuilder::build)
+            .expect("the sudoku should be valid")
+    }};
+
+    (@impl [$($cells:tt)*] [$($rows:tt)*] _ $($rest:tt)+) => {
+        sudoku!(@impl [$($cells)* 0,] [$($rows)*] $($rest)+)
     };
 
-    (@impl [$($cells:tt)*] [$($rows:tt)*] [$($given:tt)*] ($x:expr, $y:expr) $number:literal $($rest:tt)+) => {
-        sudoku!(@impl [$($cells)* $number,] [$($rows)*] [$($given)* ($x, $y),] ($x + 1, $y) $($rest)+)
+    (@impl [$($cells:tt)*] [$($rows:tt)*] $number:literal $($rest:tt)+) => {
+        sudoku!(@impl [$($cells)* $number,] [$($rows)*] $($rest)+)
     };
 
-    (@impl [$($cells:tt)*] [$($rows:tt)*] [$($given:tt)*] ($x:expr, $y:expr) ; $($rest:tt)+) => {
-        sudoku!(@impl [] [$($rows)* [$($cells)*],] [$($given)*] (0, $y + 1) $($rest)+)
+    (@impl [$($cells:tt)*] [$($rows:tt)*] ; $($rest:tt)+) => {
+        sudoku!(@impl [] [$($rows)* [$($cells)*],] $($rest)+)
     };
 
-    (@impl [$($cells:tt)+] [$($rows:tt)+] [$($given:tt)*] ($x:expr, $y:expr) ;) => {
-        Sudoku {
-            grid: [
-                $($rows)+
-                [$($cells)+],
-            ],
-            given: Box::new([$($given)*]),
-        }
+    (@impl [$($cells:tt)+] [$($rows:tt)+] ;) => {
+        $crate::SudokuBuilder::from_grid([
+            $($rows)+
+            [$($cells)+],
+        ])
+        .and_then($crate::SudokuBuilder::build)
+        .expect("the sudoku should be valid")
     };
 
     (@impl $($unknown:tt)*) => {
@@ -31,11 +74,11 @@ macro_rules! sudoku {
     };
 
     ($($input:tt)+) => {
-        sudoku!(@impl [] [] [] (0, 0) $($input)+)
+        sudoku!(@impl [] [] $($input)+)
     };
 }
 
-pub const PUZZLE: LazyCell<Sudoku> = LazyCell::new(|| {
+pub static PUZZLE: LazyLock<Sudoku> = LazyLock::new(|| {
     sudoku! {
         4 _ _ _ 9 6 2 _ 8;
         3 _ 8 1 _ _ _ 9 _;
@@ -49,99 +92,224 @@ pub const PUZZLE: LazyCell<Sudoku> = LazyCell::new(|| {
     }
 });
 
+// A sudoku with `N` rows and columns, divided into boxes of the square root of `N` by the square
+// root of `N`, such as 4-by-4, 9-by-9, and 16-by-16 sudokus.
 #[derive(Clone)]
-pub struct Sudoku {
-    pub grid: [[u8; 9]; 9],
+pub struct Sudoku<const N: usize = 9> {
+    pub grid: [[u8; N]; N],
     pub given: Box<[(usize, usize)]>,
+    pub variant: Variant,
+    pub cages: Box<[Cage]>,
 }
 
-impl From<&Sudoku> for Graph<u8> {
-    fn from(sudoku: &Sudoku) -> Self {
-        // One node for each cell, as well as nine nodes for each constraint for the given cells.
-        let mut nodes = Vec::with_capacity(90);
-
-        // Each row has 9 * 8 / 2 edges and there are 9 rows, making a total of 324 edges.
-        // By symmetry, each column has the same number of edges as the rows.
-        // Each 3-by-3 grid has 18 non-coaxial edges and there are 9 of them, making a total of 162.
-        // Combined, the rows, columns, and 3-by-3 grids form 2 * 324 + 162 = 810 edges.
-        // Each given number has 8 edges, one for each constraint node that is not equal to the
-        // given number.
-        let expected_num_edges = 810 + 8 * sudoku.given.len();
-        let mut edges = Vec::with_capacity(expected_num_edges);
-
-        for (y, row) in sudoku.grid.into_iter().enumerate() {
-            for (x, cell) in row.into_iter().enumerate() {
-                nodes.push(cell);
-
-                for i in x + 1..9 {
-                    edges.push(Edge(9 * y + x, 9 * y + i));
-                }
-
-                for j in y + 1..9 {
-                    edges.push(Edge(9 * y + x, 9 * j + x));
-                }
-
-                for (i, j) in (y + 1..(y + 3) / 3 * 3).flat_map(|j| {
-                    (x / 3 * 3..)
-                        .take(3)
-                        .filter(|i| *i != x)
-                        .map(move |i| (i, j))
-                }) {
-                    edges.push(Edge(9 * y + x, 9 * j + i));
-                }
-            }
-        }
+// The rules on top of every row, column, and box containing each value exactly once:
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Variant {
+    #[default]
+    Classic,
+    // Both main diagonals also contain each value exactly once, as in X-Sudoku.
+    Diagonal,
+}
 
-        // The constraint nodes for the given numbers.
-        nodes.extend(1..=9);
+pub type Sudoku4 = Sudoku<4>;
+pub type Sudoku16 = Sudoku<16>;
 
-        for (i, j) in sudoku.given.iter().copied() {
-            let value = sudoku.grid[j][i];
-            for v in (1..=9).filter(|v| *v != value) {
-                edges.push(Edge(9 * j + i, 80 + v as usize));
-            }
-        }
+impl<const N: usize> Sudoku<N> {
+    // The number of rows and columns of a box. Only perfect squares up to 25 are supported as
+    // sizes, so that the candidates of a cell fit in a bit set, and every value can be written as
+    // a single character.
+    pub const BOX: usize = {
+        assert!(
+            N > 0 && N <= 25 && N.isqrt() * N.isqrt() == N,
+            "the size of a sudoku must be a perfect square between 1 and 25"
+        );
+        N.isqrt()
+    };
+
+    // One node for each cell, followed by one constraint node for each value, colored with it,
+    // and then the nodes that encode the sums of the cages.
+    const CONSTRAINTS: usize = N * N;
+}
 
-        debug_assert_eq!(edges.len(), expected_num_edges);
+// Values from 10 and up are written as letters, starting with `A`.
+pub(crate) fn symbol(value: u8) -> char {
+    match value {
+        0..=9 => (b'0' + value) as char,
+        _ => (b'A' + value - 10) as char,
+    }
+}
+
+impl<const N: usize> IntoColoringInstance for Sudoku<N> {
+    fn nodes(&self) -> impl Iterator<Item = u8> {
+        self.grid
+            .into_iter()
+            .flatten()
+            .chain(1..=N as u8)
+            .chain(self.cage_gadgets().nodes)
+    }
+
+    fn constraints(&self) -> impl Iterator<Item = Edge<usize>> {
+        self.labeled_constraints().map(|(edge, _)| edge)
+    }
 
-        Self {
-            nodes: nodes.into(),
-            edges: edges.into(),
+    fn domain(&self) -> ColorDomain {
+        ColorDomain::new(N as u8)
+    }
+}
+
+// The rule of the sudoku that an edge of its graph comes from:
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deftly)]
+#[derive_deftly(Bytes, Describe)]
+pub enum Constraint {
+    Row,
+    Column,
+    Box,
+    Given,
+    Diagonal,
+    Cage,
+}
+
+impl Display for Constraint {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Row => write!(f, "row"),
+            Self::Column => write!(f, "column"),
+            Self::Box => write!(f, "box"),
+            Self::Given => write!(f, "given value"),
+            Self::Diagonal => write!(f, "diagonal"),
+            Self::Cage => write!(f, "cage"),
         }
     }
 }
 
-impl Display for Sudoku {
+impl<const N: usize> Sudoku<N> {
+    fn labeled_constraints(&self) -> impl Iterator<Item = (Edge<usize>, Constraint)> {
+        // Each cell is connected to every other cell in its row, column, and box, and diagonals if
+        // they are constrained. The edges that are shared between them are dropped when building
+        // the graph.
+        let diagonal = self.variant == Variant::Diagonal;
+        let peers = positions::<N>().flat_map(move |(x, y)| {
+            let diagonals = [x == y, x + y == N - 1]
+                .into_iter()
+                .enumerate()
+                .filter(move |&(_, on)| diagonal && on)
+                .map(|(index, _)| (Unit::Diagonal, index, Constraint::Diagonal));
+            [
+                (Unit::Row, y, Constraint::Row),
+                (Unit::Column, x, Constraint::Column),
+                (Unit::Box, Unit::box_of::<N>((x, y)), Constraint::Box),
+            ]
+            .into_iter()
+            .chain(diagonals)
+            .flat_map(|(unit, index, constraint)| {
+                unit.cells::<N>(index).map(move |peer| (peer, constraint))
+            })
+            .filter(move |&(peer, _)| peer != (x, y))
+            .map(move |((i, j), constraint)| (Edge(N * y + x, N * j + i), constraint))
+        });
+
+        // The constraint nodes are connected to each other, so that they have distinct colors in
+        // any coloring, which anchors each color to a value. Each given cell is then connected to
+        // the constraint nodes of every other value.
+        let anchors = (0..N).flat_map(|a| {
+            (a + 1..N).map(move |b| {
+                let edge = Edge(Self::CONSTRAINTS + a, Self::CONSTRAINTS + b);
+                (edge, Constraint::Given)
+            })
+        });
+        let givens = self.given.iter().flat_map(|&(x, y)| {
+            let value = self.grid[y][x];
+            (1..=N as u8).filter(move |&v| v != value).map(move |v| {
+                let edge = Edge(N * y + x, Self::CONSTRAINTS + v as usize - 1);
+                (edge, Constraint::Given)
+            })
+        });
+
+        // The values of a cage are distinct, so each cell is connected to every other cell in its
+        // cage, and to the constraint nodes of the values that no set of distinct values adding up
+        // to the sum contains. The sum itself is encoded by the gadgets of `cage_gadgets`.
+        let cages = self.cages.iter().flat_map(|cage| {
+            let digits = cage.digits(N);
+            cage.cells.iter().flat_map(move |&(x, y)| {
+                let peers = cage
+                    .cells
+                    .iter()
+                    .filter(move |&&peer| peer != (x, y))
+                    .map(move |&(i, j)| Edge(N * y + x, N * j + i));
+                let values = (1..=N)
+                    .filter(move |&v| digits & 1 << v == 0)
+                    .map(move |v| Edge(N * y + x, Self::CONSTRAINTS + v - 1));
+                peers.chain(values).map(|edge| (edge, Constraint::Cage))
+            })
+        });
+        let gadgets = self
+            .cage_gadgets()
+            .edges
+            .into_iter()
+            .map(|edge| (edge, Constraint::Cage));
+
+        peers
+            .chain(anchors)
+            .chain(givens)
+            .chain(cages)
+            .chain(gadgets)
+    }
+
+    // The edges of the graph of the sudoku, in the same order, each labeled with the rule it
+    // comes from. Edges shared between several rules are labeled with the first of row, column,
+    // box, diagonal, and cage.
+    pub fn labeled_edges(&self) -> Box<[LabeledEdge<Constraint>]> {
+        let mut seen = HashSet::new();
+        self.labeled_constraints()
+            .filter(|&(edge, _)| seen.insert(edge))
+            .map(|(Edge(a, b), label)| LabeledEdge {
+                edge: Edge::new(a.min(b), a.max(b)).expect("all nodes fit in a u32"),
+                label,
+            })
+            .collect()
+    }
+}
+
+// Where a node of the graph of a sudoku comes from:
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Node {
+    Cell { x: usize, y: usize },
+    Constraint { value: u8 },
+    Cage { cage: usize },
+}
+
+impl Display for Node {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        writeln!(f, "╔═══╤═══╤═══╦═══╤═══╤═══╦═══╤═══╤═══╗")?;
-        for (y, row) in self.grid.into_iter().enumerate() {
-            if y == 3 || y == 6 {
-                writeln!(f, "╠═══╪═══╪═══╬═══╪═══╪═══╬═══╪═══╪═══╣")?;
-            } else if y > 0 {
-                writeln!(f, "╟───┼───┼───╫───┼───┼───╫───┼───┼───╢")?;
-            }
-
-            write!(f, "║")?;
-            for (x, cell) in row.into_iter().enumerate() {
-                if x == 3 || x == 6 {
-                    write!(f, "║")?;
-                } else if x > 0 {
-                    write!(f, "│")?
-                }
-
-                match cell {
-                    0 => write!(f, "   ")?,
-                    value => {
-                        if self.given.contains(&(x, y)) {
-                            write!(f, "\x1b[1;7m {value} \x1b[0m")?
-                        } else {
-                            write!(f, " {value} ")?
-                        }
-                    }
-                }
-            }
-            writeln!(f, "║")?
+        match self {
+            Self::Cell { x, y } => write!(f, "cell ({x}, {y})"),
+            Self::Constraint { value } => write!(f, "constraint node {value}"),
+            Self::Cage { cage } => write!(f, "node of cage {cage}"),
         }
-        write!(f, "╚═══╧═══╧═══╩═══╧═══╧═══╩═══╧═══╧═══╝")
+    }
+}
+
+impl<const N: usize> Sudoku<N> {
+    // The graph of the sudoku, with each node labeled with where it comes from.
+    pub fn labeled_graph(&self) -> Labeled<u8, Node> {
+        let labels = positions::<N>()
+            .map(|(x, y)| Node::Cell { x, y })
+            .chain((1..=N as u8).map(|value| Node::Constraint { value }))
+            .chain(
+                self.cage_gadgets()
+                    .cages
+                    .into_iter()
+                    .map(|cage| Node::Cage { cage }),
+            )
+            .collect();
+
+        Labeled::new(Graph::from(self), labels).expect("there is a label for every node")
+    }
+}
+
+impl<const N: usize> From<&Sudoku<N>> for Graph<u8> {
+    fn from(sudoku: &Sudoku<N>) -> Self {
+        sudoku
+            .to_graph()
+            .expect("all edges connect distinct nodes of the grid")
     }
 }
@@ -0,0 +1,182 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::Sudoku;
+
+// How hard a puzzle of the library is to solve by hand:
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Difficulty {
+    // Solved by naked and hidden singles alone, starting from 36 givens.
+    Easy,
+    // Solved by naked and hidden singles alone, but starting from only 28 givens.
+    Medium,
+    // Needs more than naked and hidden singles at some point.
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Self; 3] = [Self::Easy, Self::Medium, Self::Hard];
+
+    fn puzzles(self) -> &'static [&'static str] {
+        match self {
+            Self::Easy => &EASY,
+            Self::Medium => &MEDIUM,
+            Self::Hard => &HARD,
+        }
+    }
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Easy => write!(f, "easy"),
+            Self::Medium => write!(f, "medium"),
+            Self::Hard => write!(f, "hard"),
+        }
+    }
+}
+
+// A puzzle of the library, numbered from 1 within its difficulty. Every puzzle has a unique
+// solution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Entry {
+    pub difficulty: Difficulty,
+    pub number: usize,
+    puzzle: &'static str,
+}
+
+impl Entry {
+    // The difficulty and the number, such as `easy-1`.
+    pub fn id(&self) -> String {
+        format!("{}-{}", self.difficulty, self.number)
+    }
+
+    pub fn puzzle(&self) -> Sudoku {
+        self.puzzle
+            .parse()
+            .expect("the puzzles of the library are valid")
+    }
+}
+
+// Every puzzle of the library, from easy to hard.
+pub fn entries() -> impl Iterator<Item = Entry> {
+    Difficulty::ALL.into_iter().flat_map(by_difficulty)
+}
+
+pub fn by_difficulty(difficulty: Difficulty) -> impl Iterator<Item = Entry> {
+    difficulty
+        .puzzles()
+        .iter()
+        .enumerate()
+        .map(move |(i, &puzzle)| Entry {
+            difficulty,
+            number: i + 1,
+            puzzle,
+        })
+}
+
+pub fn find(id: &str) -> Option<Entry> {
+    entries().find(|entry| entry.id() == id)
+}
+
+pub fn get(id: &str) -> Option<Sudoku> {
+    find(id).map(|entry| entry.puzzle())
+}
+
+const EASY: [&str; 30] = [
+    "..3.....46.7.3...5.9..463...6.493.513...2..969.5.78.4....7.26.88..3.....52..6.4.7",
+    "..53....89...6.2.7768.593....74..1....1..374.24..1.58....1.5..38..9.6..5..97..86.",
+    "...56.12..97...3..51...3.7.468..1.....97..584...94..6..4...9612.7...2.3..316.5..7",
+    "7..8..395.4.159.8...53.6.....64...7.5..76..13.3....9...78.....939.6..857..2..763.",
+    "3..9.......8.5....6542....9..3896..7..9.156..465..7.9.542731...136....7.9.....5.3",
+    ".....6.2.4..1.986.97...851.3...9.7....17.4...76..82.3...7.2...5.549..3...13465..9",
+    "19758...35...1.7.6..293.....21.....5.63.529.49.5..3.1...9..1.3..1.....2.2.436...7",
+    ".8.142..6.5.68.....2.95.7.82...39571.3..21.895....64.2.1......4....95.1.87.....6.",
+    "6...1...8.8..957......6.139.5.68.2..7.25..6...16.....312..5....5387.6.21..71..84.",
+    "..6..8....3.24...981.....3.6...87..45.3412.969.8563..2.62...9.....1.642.....29..3",
+    "15497..62...5.3...37..46..5.6..3..4.845..12.32...54986.8.3..............936..7..8",
+    "..6...7...142..8.5..8.9..41.7...498.2..9.741.48.12.5.71.2.3...8...76...48..41....",
+    "...5.1.422....49.66438..7....7...15.9...1.4......57.9....1.58.9.9..762..1.698..74",
+    "8364.29..2.5.83..1.14.56.....9.3.....875.....4.3.275....23....83..26..4....8.9.53",
+    "..9...386.648........61.4.7......23.4...5867.25.......3..7..5.16915..723547.2...8",
+    "2........68...72939..6.2..8.1....4.584.213...36.5.....4.678.9.25..42....12..36.7.",
+    "1.5...436.2..6.197.4...1.5..8..5721.7..3.45......1..4.961..23...381...7..7....62.",
+    ".1..4..37....3..293.........5....27...81.25.3.32...9.69...2..6..21853.9.57.6948.2",
+    "71..6.58....25..3.56....2..4.6.2.89.2..49...31..6.8.52.7.....416..984....4..16..8",
+    "..7.821.3.1.6.3..838...5.271.583...46.8...9..7.......187...62495..721......94....",
+    ".2..69.7...6..4..2.5.2.36897123...65.4..7..2163.1..........7..6.....195.1795...4.",
+    ".......9.3.1.9.2.42.43.1.5.4..613.2.1.9.42..852.9..4....71.5...6..8...719.5.7...2",
+    "691.....3..865..72....146.8....6...1.2.5.8.47.3..7298.964.2.8.5....4.2....2....64",
+    "..2.31.8.....9635..7354...........6.....8.2452653..8....186...98..95.1..92..135.8",
+    "54..2.......9.5......8..1536.174.3...532..8.1....1..6..35.8729.2..439.1.98.1....6",
+    ".7....9..6.5.89.1.....34.8712.36....3891.24.5..6..8..12...538..89.2......47.9..3.",
+    ".2.31..9..14.....6....2.1..63..4897.9.5.6..3.472.....1.6.4.18..1.3985...85.67....",
+    "..3.7......9.461.5.56..1.825..7..8..6875...192316.954.....9....7.8..2.53.1..5....",
+    "45.3.17.8.37.....26...2835..7..1..46..4.3.8.9..64....37....24852.1..5.37.......2.",
+    "..1869.27632.4..1.98...35..8..9357...5.....8..1.....5....4...38.683.21..2...5..74",
+];
+
+const MEDIUM: [&str; 30] = [
+    "...63....1...7...48...4273...........714.3.6.58......2..498..7...2...5.9....671..",
+    ".69....3....94.6..1.2.3...7.5..6....346.5.2.88.....15...4.93.........82..3......4",
+    ".791.82.....2.....4....78......9.126..75...8..91.......2...93...1...45.7...35.4..",
+    "..9.......4.6....22...5.9.798...3....265.9.38..58....6..3...2..7..4.285........4.",
+    ".29....3.6.3.5..1.4....17...967...84...2...53.8..63..1...6..1....4....9....5.7...",
+    "..7..1..6621.48.....5.........2....9863....1.7.25..34........67.4.13...5....6...1",
+    ".......4.6...8.....9412.7.....5..2.395.......3.287.61.23..4........12..9.....83.2",
+    "4........239..8.....8.249..7.6...1.....6.25......87..2.8.4...9..7..6...15.48..3..",
+    "86..31.72.....2...13..5.9..64..2.8.79..4....357.............2.8.....6.....4.13.5.",
+    "367.9....59.1.4.....2.5...9......867.....5..3...84....7..4..5...24...9..95..3.7..",
+    "62.4.5........8..2..9..7..3...841...2.57.61..1.....78..4......6.386.4......2...4.",
+    "19.....4.........5..42...7.641..83......2....3...1..5726..........56.7...594.1.26",
+    "2....6.......8.....8..5.43...1....87..957.26..7.8....4...1..6.3..7.95...6..23..5.",
+    "29....4.5.7....3..46...2..95..9....8...4..2.......89..754.2..8.6....3...1...65.4.",
+    "....359.6....8..4.81.....7..9........43....1....6...98..9.5.4..1.2.4786..84..6...",
+    ".5.791............9....35..6..5...7.7...6...3819..76.......9.....36.514..96.2.3..",
+    "1....6....8.79....2.4.........18..6........75..8...1.3.9...4.51...9517.65.3.2.9..",
+    "9...68...5...7.89.4....127.2....95..3......6....736..4.....298..94..7....8......5",
+    ".3.54..9.....69....4......8.12.3.........521..7..1....32.684..5.6...3.......9264.",
+    "6...5...4..28.....3...9..8...8.6.9...1..7..6.9.5...8.38....9......5...1..791.3.28",
+    "83.5..1.9..............95...9863.2....7.2..1..2.815...5...7.4.1.8......62......58",
+    "..3.....961....7...8...9.4.7..4..1.33.519...8....38.9....2.63..........7..1.4.9.5",
+    "6......5.5..4..2....1....6.4.5.2..8..1.6..37.2..78.....2.974........163..5.3....4",
+    "4.9..6..5.5..23..1..6.5..89..781...4..26......8....12.....948.....1..6.2...5.....",
+    "8.7.....6...17.9.8.42....7.2.3..9.......5.1..46.81......49......31..25..9...31...",
+    "9.83.7..51......7..6..4.8292....8..3..56.......3..4.8....8...92..24......3..5.1..",
+    "3..1.76..9.83...2..12.....77.9....56....73..9......8....1.3...4.9..16......8.9.6.",
+    ".1742..58.62..........75.......1.9.3.9.3....7..3....4......1...384..96.1..6...7.4",
+    ".592..7.........9..21....6.5.4896....62.4..5.7..5..4....5..........1.24.8..65...1",
+    ".7..9......6.7....82...3.6....16.2.89...2.....1.38.4....2..46.......21.55.4...8.3",
+];
+
+const HARD: [&str; 30] = [
+    "....3...243.....9..7.61.....8...5.......7.9.....9..76.3.........5....6..6.9.8.2.3",
+    "18.2.497..9.5....65.4.....24.3....9.85...2.....1..8.......7.68..1......7...9..12.",
+    "1.7...38...28...7......5...9......31.78.2....24.6....8...4.2...7.....5...9..1....",
+    "3.12..57.5...........1..9....3....2..7.8.1...2...7.8.6.6.9.73..7.9.63.1..........",
+    "...........15.6....43....2....2.18..2..8.37.5.9.....6...9..5.7....4872...1....5..",
+    "...1.2...1.3..8....6.......9........8.4.6.25...2..54.8.7.....1659..........7..3..",
+    "5.89.....279.5.......37......16.75....6.21..7.........9..4...3.....6.15...5...7.8",
+    "...3......9.........6.18.75.546......12..5.8.9....3.4....5.....1254..3.....1.6..9",
+    "8....4.96....6.....2..598...9.3.......8....5.1.......9986.....2..42...3.5...1..67",
+    "5.7....2..6..7......3...9.......6.54...1...8.8...4.21.4....5...21.3...9.....6....",
+    "..2.5.46.....2.....9.6...2....56.3..5.7..16....6....54.3...4...8........1.....7.3",
+    "8......7...3.....1.....4896.............8.1.5.5...6...9....83..7...69....6.14...8",
+    "....6.3....2..7.546.......8.7...4.....8..946...6.1.8...8........1..78.....5.327..",
+    "........4..4.3.7....9.12.........8..3..598......7...2.7....5.68..32....9.5...9..3",
+    "...6.358...9.1..2...4.....19...54...8..9.......6..1.........9...71..68.........53",
+    "...189.....14.....89...7.........42.372.1........9.67..1....8....782.5..65...3.4.",
+    "9.....4.25......9...3.....5...6........8.1..7..2.59....8.4.29...7.9..6...3..1..74",
+    ".6...1.9....5..7..4.3........6...2..2..79..83.4..2.5............5....17813...9.2.",
+    ".3...5.6...19.....7.5.4.1....8....54.....738....4.....1.48.9..6......4...5.....38",
+    "..6.75..22...34.5.5.7....6.........67...62.89.8....4.7..8.4....9....7..5...2.6...",
+    "..2...4..5.4.12....68..5.7..2...6........1.9..8.3.......317...4.9....61.....2.9..",
+    "...4....8..9..5...4.2...1...71..6..4.......2......85....5.9..3.73..5..6.9....1...",
+    "369....1....7....44.....62..32.9....5..4......4.1....6.51..734..9...5........89..",
+    "....3..5...18...9....6....8....48..3.9....56435...............9.7..85.4.83..2....",
+    "2.3.5.4........5......89......49....7.......8..1..3..43.652.....9..7.81........2.",
+    "....2...76..17.......4...9........6........23598....1..5...39..9.7.....54..7...32",
+    "...986.1.2.91.7......4......5.....96..1...4....7.......2...8.59.......6..4.5..387",
+    ".........5.4..19...19...52824....7......79..2....86.3..7...46....6...245........9",
+    "8....21....198..7..25....3...9..47..5..6.......4..3..6.7....9.......5.4...2.4....",
+    "7....3...3...9..6..1.8....9..1...97.8..6..1....71.....4..2....5..2.5..91..8...6..",
+];
@@ -0,0 +1,231 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use crate::{Hint, Sudoku, Technique, positions, symbol};
+
+// One step of solving a sudoku by logic alone, either filling in a cell with a single, or ruling
+// out candidates of other cells with a pattern starting at the cell. The candidates that are
+// ruled out by filling in a cell are those of its peers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub technique: Technique,
+    pub cell: (usize, usize),
+    // The value that the cell is filled in with, for singles:
+    pub value: Option<u8>,
+    pub eliminated: Box<[((usize, usize), u8)]>,
+}
+
+// Where solving by logic alone stopped, because no technique applies:
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Stuck<const N: usize = 9> {
+    pub grid: [[u8; N]; N],
+    pub steps: Vec<Step>,
+}
+
+// The candidates of each cell as bit sets, where bit `v` is set if `v` can still be placed in it,
+// and 0 for filled-in cells:
+type Marks<const N: usize> = [[u32; N]; N];
+
+impl<const N: usize> Sudoku<N> {
+    // Solves the sudoku step by step with the techniques of `Technique`, trying the simplest
+    // first, like a person would. Returns the solution along with the steps leading up to it, or
+    // where it got stuck if the techniques aren't enough, or if the filled-in cells break a rule.
+    pub fn solve_logically(&self) -> Result<(Self, Vec<Step>), Stuck<N>> {
+        let mut sudoku = self.clone();
+        let mut steps = Vec::new();
+        // The candidates ruled out by patterns, which can't be told from the filled-in cells:
+        let mut eliminated = [[0; N]; N];
+
+        while let Some(marks) = sudoku.marks(&eliminated) {
+            if sudoku.is_complete() {
+                return Ok((sudoku, steps));
+            }
+            let Some(mut step) = sudoku.next_step(&marks) else {
+                break;
+            };
+
+            match step.value {
+                Some(value) => {
+                    let (x, y) = step.cell;
+                    sudoku.grid[y][x] = value;
+                    if let Some(after) = sudoku.marks(&eliminated) {
+                        step.eliminated = ruled_out(&marks, &after, step.cell);
+                    }
+                }
+                None => {
+                    for &((x, y), value) in &step.eliminated {
+                        eliminated[y][x] |= 1 << value;
+                    }
+                }
+            }
+            steps.push(step);
+        }
+
+        Err(Stuck {
+            grid: sudoku.grid,
+            steps,
+        })
+    }
+
+    pub(crate) fn marks(&self, eliminated: &Marks<N>) -> Option<Marks<N>> {
+        let candidates = self.candidates()?;
+        let mut marks = [[0; N]; N];
+        for ((x, y), value) in self.cells() {
+            if value == 0 {
+                marks[y][x] = candidates.get((x, y)) & !eliminated[y][x];
+            }
+        }
+
+        Some(marks)
+    }
+
+    pub(crate) fn next_step(&self, marks: &Marks<N>) -> Option<Step> {
+        let has = |(x, y): (usize, usize), value: u8| marks[y][x] & 1 << value != 0;
+        let step = |technique, cell, value, eliminated: Vec<_>| Step {
+            technique,
+            cell,
+            value,
+            eliminated: eliminated.into(),
+        };
+
+        for (x, y) in positions::<N>() {
+            if marks[y][x].count_ones() == 1 {
+                let value = marks[y][x].trailing_zeros() as u8;
+                return Some(step(Technique::NakedSingle, (x, y), Some(value), vec![]));
+            }
+        }
+
+        for (unit, index) in self.units() {
+            for value in 1..=N as u8 {
+                let mut places = unit.cells::<N>(index).filter(|&cell| has(cell, value));
+                if let (Some(cell), None) = (places.next(), places.next()) {
+                    let technique = Technique::HiddenSingle(unit);
+                    return Some(step(technique, cell, Some(value), vec![]));
+                }
+            }
+        }
+
+        for (unit, index) in self.units() {
+            for value in 1..=N as u8 {
+                let places: Vec<_> = unit
+                    .cells::<N>(index)
+                    .filter(|&cell| has(cell, value))
+                    .collect();
+                let [first, ..] = places[..] else {
+                    continue;
+                };
+                for (other, other_index) in self.units_of(first) {
+                    let cells: Vec<_> = other.cells::<N>(other_index).collect();
+                    if (other, other_index) == (unit, index)
+                        || !places.iter().all(|place| cells.contains(place))
+                    {
+                        continue;
+                    }
+
+                    let eliminated: Vec<_> = cells
+                        .into_iter()
+                        .filter(|&cell| !places.contains(&cell) && has(cell, value))
+                        .map(|cell| (cell, value))
+                        .collect();
+                    if !eliminated.is_empty() {
+                        let technique = Technique::LockedCandidates(unit, other);
+                        return Some(step(technique, first, None, eliminated));
+                    }
+                }
+            }
+        }
+
+        for (unit, index) in self.units() {
+            let cells: Vec<_> = unit.cells::<N>(index).collect();
+            let pairs = cells
+                .iter()
+                .enumerate()
+                .flat_map(|(i, &a)| cells[i + 1..].iter().map(move |&b| (a, b)));
+            for ((x, y), (i, j)) in pairs {
+                let pair = marks[y][x];
+                if pair.count_ones() != 2 || marks[j][i] != pair {
+                    continue;
+                }
+
+                let eliminated: Vec<_> = cells
+                    .iter()
+                    .filter(|&&cell| cell != (x, y) && cell != (i, j))
+                    .flat_map(|&cell| {
+                        (1..=N as u8)
+                            .filter(move |&value| pair & 1 << value != 0 && has(cell, value))
+                            .map(move |value| (cell, value))
+                    })
+                    .collect();
+                if !eliminated.is_empty() {
+                    return Some(step(Technique::NakedPair(unit), (x, y), None, eliminated));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+// The candidates of other cells than `cell` that are set in `before` but not in `after`:
+fn ruled_out<const N: usize>(
+    before: &Marks<N>,
+    after: &Marks<N>,
+    cell: (usize, usize),
+) -> Box<[((usize, usize), u8)]> {
+    positions::<N>()
+        .filter(|&other| other != cell)
+        .flat_map(|(x, y)| {
+            let lost = before[y][x] & !after[y][x];
+            (1..=N as u8)
+                .filter(move |&value| lost & 1 << value != 0)
+                .map(move |value| ((x, y), value))
+        })
+        .collect()
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let Self {
+            technique,
+            cell,
+            value,
+            eliminated,
+        } = self;
+        if let Some(value) = *value {
+            return Hint {
+                cell: *cell,
+                value,
+                technique: *technique,
+            }
+            .fmt(f);
+        }
+
+        let (x, y) = cell;
+        write!(f, "Ruled out by {technique} at cell ({x}, {y}):")?;
+        for (i, ((x, y), value)) in eliminated.iter().enumerate() {
+            let separator = if i == 0 { " " } else { ", " };
+            write!(f, "{separator}{} from ({x}, {y})", symbol(*value))?;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> Error for Stuck<N> {}
+
+impl<const N: usize> Display for Stuck<N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let empty = self
+            .grid
+            .iter()
+            .flatten()
+            .filter(|&&value| value == 0)
+            .count();
+        write!(
+            f,
+            "no technique applies after {} steps, with {empty} empty cells left",
+            self.steps.len()
+        )
+    }
+}
@@ -0,0 +1,193 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use crate::Sudoku;
+
+// What a character of a puzzle stands for: the value of a cell, where 0 is a blank, a character
+// that is skipped, or an invalid one. Values are only checked against the size of the sudoku by
+// the caller.
+enum Token {
+    Cell(u8),
+    Skip,
+    Invalid,
+}
+
+const fn token(character: char) -> Token {
+    match character {
+        '.' | '0' | '_' => Token::Cell(0),
+        c if c.is_whitespace() || matches!(c, '|' | '-' | '+' | '/') => Token::Skip,
+        c => match c.to_digit(36) {
+            Some(value) => Token::Cell(value as u8),
+            None => Token::Invalid,
+        },
+    }
+}
+
+// Parses the common format of one digit per cell, row by row, where `.`, `0`, or `_` is a blank,
+// and values from 10 and up are letters starting with `A`. Whitespace, the box borders `|`, `-`,
+// and `+`, and `/` between rows are skipped, so that the cells can be spread over several lines
+// or divided into rows on one. Every filled-in cell is given.
+impl<const N: usize> FromStr for Sudoku<N> {
+    type Err = ParseSudokuError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut grid = [[0; N]; N];
+        let mut cells = 0;
+        for (line, text) in s.lines().enumerate() {
+            for (column, character) in text.chars().enumerate() {
+                let value = match token(character) {
+                    Token::Cell(value) if value as usize <= N => value,
+                    Token::Skip => continue,
+                    _ => {
+                        return Err(ParseSudokuError::InvalidCharacter {
+                            character,
+                            line: line + 1,
+                            column: column + 1,
+                        });
+                    }
+                };
+
+                if cells == N * N {
+                    return Err(ParseSudokuError::TooManyCells {
+                        expected: N * N,
+                        line: line + 1,
+                        column: column + 1,
+                    });
+                }
+
+                grid[cells / N][cells % N] = value;
+                cells += 1;
+            }
+        }
+
+        if cells < N * N {
+            return Err(ParseSudokuError::TooFewCells {
+                expected: N * N,
+                cells,
+            });
+        }
+
+        Ok(Self::from_grid(grid).expect("values are checked while parsing"))
+    }
+}
+
+// The same format as `FromStr`, parsed at compile time by the `sudoku!` macro, so errors can't
+// point to where they are. Only ASCII characters are accepted. The size of the sudoku is the
+// square root of the number of cells.
+#[doc(hidden)]
+pub const fn cell_count(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let mut cells = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if !matches!(token(bytes[i] as char), Token::Skip) {
+            cells += 1;
+        }
+        i += 1;
+    }
+    cells
+}
+
+#[doc(hidden)]
+pub const fn grid_from_str<const N: usize>(s: &str) -> [[u8; N]; N] {
+    let bytes = s.as_bytes();
+    let mut grid = [[0; N]; N];
+    let mut cells = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let value = match token(bytes[i] as char) {
+            _ if !bytes[i].is_ascii() => panic!("the sudoku has a character that is not ASCII"),
+            Token::Cell(value) if value as usize <= N => value,
+            Token::Skip => {
+                i += 1;
+                continue;
+            }
+            _ => panic!("the sudoku has an invalid character"),
+        };
+        if cells == N * N {
+            panic!("the number of cells of the sudoku is not a square");
+        }
+        grid[cells / N][cells % N] = value;
+        cells += 1;
+        i += 1;
+    }
+    if cells < N * N {
+        panic!("the number of cells of the sudoku is not a square");
+    }
+
+    // The same check as `SudokuBuilder::build` for the classic rules, which can't be called here.
+    let size = N.isqrt();
+    let mut a = 0;
+    while a < N * N {
+        let mut b = a + 1;
+        while b < N * N {
+            let ((x, y), (i, j)) = ((a % N, a / N), (b % N, b / N));
+            let peers = x == i || y == j || (x / size == i / size && y / size == j / size);
+            if peers && grid[y][x] != 0 && grid[y][x] == grid[j][i] {
+                panic!("a value is repeated in a row, column, or box of the sudoku");
+            }
+            b += 1;
+        }
+        a += 1;
+    }
+
+    grid
+}
+
+#[derive(Debug)]
+pub enum ParseSudokuError {
+    InvalidCharacter {
+        character: char,
+        line: usize,
+        column: usize,
+    },
+    TooManyCells {
+        expected: usize,
+        line: usize,
+        column: usize,
+    },
+    TooFewCells {
+        expected: usize,
+        cells: usize,
+    },
+    // An error in one of several puzzles, counting from 1:
+    InPuzzle {
+        number: usize,
+        error: Box<ParseSudokuError>,
+    },
+    NoPuzzles,
+}
+
+impl Error for ParseSudokuError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InPuzzle { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ParseSudokuError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidCharacter {
+                character,
+                line,
+                column,
+            } => write!(f, "invalid character {character:?} at {line}:{column}"),
+            Self::TooManyCells {
+                expected,
+                line,
+                column,
+            } => write!(f, "more than {expected} cells, starting at {line}:{column}"),
+            Self::TooFewCells { expected, cells } => {
+                write!(f, "expected {expected} cells but found {cells}")
+            }
+            Self::InPuzzle { number, error } => write!(f, "puzzle {number}: {error}"),
+            Self::NoPuzzles => write!(f, "no puzzles found"),
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use std::{fs, io, path::Path};
+
+use crate::{ParseSudokuError, Sudoku};
+
+// The file formats of SadMan Sudoku: `.sdk` files hold one puzzle as nine lines of nine cells,
+// after header lines starting with `#`, and `.sdm` files hold one puzzle per line.
+impl Sudoku {
+    pub fn from_sdk(s: &str) -> Result<Self, ParseSudokuError> {
+        // Newer files divide the puzzle from the state of solving it in sections, such as
+        // `[Puzzle]` and `[State]`, of which only the first is needed.
+        let mut section = None;
+        let puzzle: Vec<_> = s
+            .lines()
+            .map(|line| {
+                if line.starts_with('[') {
+                    section = Some(line.trim());
+                    ""
+                } else if line.starts_with('#') || section.is_some_and(|s| s != "[Puzzle]") {
+                    ""
+                } else {
+                    line
+                }
+            })
+            .collect();
+
+        // Header lines are kept as blank lines, so that errors point to the right line.
+        puzzle.join("\n").parse()
+    }
+
+    pub fn to_sdk(&self) -> String {
+        let compact = self.to_compact();
+        (0..9)
+            .map(|y| format!("{}\n", &compact[9 * y..9 * (y + 1)]))
+            .collect()
+    }
+
+    pub fn from_sdm(s: &str) -> Result<Vec<Self>, ParseSudokuError> {
+        s.lines()
+            .filter(|line| !line.trim().is_empty())
+            .enumerate()
+            .map(|(i, line)| {
+                line.parse().map_err(|err| ParseSudokuError::InPuzzle {
+                    number: i + 1,
+                    error: Box::new(err),
+                })
+            })
+            .collect()
+    }
+
+    pub fn to_sdm(puzzles: &[Self]) -> String {
+        puzzles
+            .iter()
+            .map(|puzzle| puzzle.to_compact().replace('.', "0") + "\n")
+            .collect()
+    }
+
+    // Reads a puzzle from an `.sdm` file, taking the first one, or otherwise from an `.sdk` file,
+    // which also accepts a puzzle on a single line.
+    pub fn load<P>(path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+        let invalid_data = |err| io::Error::new(io::ErrorKind::InvalidData, err);
+
+        if path.extension().is_some_and(|extension| extension == "sdm") {
+            Self::from_sdm(&contents)
+                .map_err(invalid_data)?
+                .into_iter()
+                .next()
+                .ok_or_else(|| invalid_data(ParseSudokuError::NoPuzzles))
+        } else {
+            Self::from_sdk(&contents).map_err(invalid_data)
+        }
+    }
+}
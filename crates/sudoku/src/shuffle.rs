@@ -0,0 +1,82 @@
+use std::array;
+
+use rand::{Rng, seq::SliceRandom};
+
+use crate::{Cage, Sudoku, Variant};
+
+impl<const N: usize> Sudoku<N> {
+    // An equivalent sudoku, with the digits relabeled, the bands and stacks swapped, the rows and
+    // columns swapped within them, and the grid rotated, all at random. Swapping rows or columns
+    // would move cells off the diagonals, and relabeling the digits would change the sums of
+    // cages, so those are left out for sudokus with such rules.
+    pub fn shuffled<R>(&self, rng: &mut R) -> Self
+    where
+        R: Rng + ?Sized,
+    {
+        let (rows, columns) = if self.variant == Variant::Diagonal {
+            (array::from_fn(|i| i), array::from_fn(|i| i))
+        } else {
+            (Self::shuffled_lines(rng), Self::shuffled_lines(rng))
+        };
+        let turns = rng.random_range(0..4);
+        let moved = |(x, y): (usize, usize)| {
+            let (mut x, mut y) = (columns[x], rows[y]);
+            for _ in 0..turns {
+                (x, y) = (N - 1 - y, x);
+            }
+            (x, y)
+        };
+
+        let mut digits: [u8; N] = array::from_fn(|i| i as u8 + 1);
+        if self.cages.is_empty() {
+            digits.shuffle(rng);
+        }
+
+        let mut grid = [[0; N]; N];
+        for (cell, value) in self.cells() {
+            let (x, y) = moved(cell);
+            grid[y][x] = match value {
+                0 => 0,
+                _ => digits[value as usize - 1],
+            };
+        }
+
+        Self {
+            grid,
+            given: self.given.iter().map(|&cell| moved(cell)).collect(),
+            variant: self.variant,
+            cages: self
+                .cages
+                .iter()
+                .map(|cage| Cage {
+                    cells: cage.cells.iter().map(|&cell| moved(cell)).collect(),
+                    sum: cage.sum,
+                })
+                .collect(),
+        }
+    }
+
+    // Where each row or column is moved to, keeping the ones of a band or stack together.
+    fn shuffled_lines<R>(rng: &mut R) -> [usize; N]
+    where
+        R: Rng + ?Sized,
+    {
+        let mut bands: Vec<_> = (0..Self::BOX).collect();
+        bands.shuffle(rng);
+        let lines: Vec<_> = bands
+            .into_iter()
+            .flat_map(|band| {
+                let mut lines: Vec<_> = (0..Self::BOX).map(|i| Self::BOX * band + i).collect();
+                lines.shuffle(rng);
+                lines
+            })
+            .collect();
+
+        // The lines are listed in their new order, so the position of each is the inverse.
+        let mut positions = [0; N];
+        for (position, line) in lines.into_iter().enumerate() {
+            positions[line] = position;
+        }
+        positions
+    }
+}
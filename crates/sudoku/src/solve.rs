@@ -0,0 +1,224 @@
+use crate::{Sudoku, Unit, Variant};
+
+// The candidates of each unit as bit sets, where bit `v` is set if `v` can still be placed in it.
+pub(crate) struct Candidates<const N: usize> {
+    rows: [u32; N],
+    columns: [u32; N],
+    boxes: [u32; N],
+    // The main diagonal followed by the anti-diagonal, if they are constrained:
+    diagonals: Option<[u32; 2]>,
+    cages: Box<[CageCandidates]>,
+    // The index of the cage of each cell, if any:
+    cage_of: [[Option<usize>; N]; N],
+}
+
+// What has been placed in a cage so far:
+struct CageCandidates {
+    used: u32,
+    sum: u32,
+    empty: usize,
+    target: u32,
+}
+
+impl CageCandidates {
+    // The values that can be placed in an empty cell of the cage such that the remaining empty
+    // cells can still make up the rest of the sum with distinct values, judging only by how many
+    // of them are left.
+    fn get(&self, size: u32) -> u32 {
+        let rest = self.target.saturating_sub(self.sum);
+        let others = self.empty.saturating_sub(1) as u32;
+        // A cage has no more cells than there are values, but a larger one must not overflow:
+        let min = others.saturating_mul(others + 1) / 2;
+        let max = others.saturating_mul((2 * size + 1).saturating_sub(others)) / 2;
+
+        (1..=size)
+            .filter(|&value| self.used & 1 << value == 0)
+            .filter(|&value| {
+                if others == 0 {
+                    value == rest
+                } else {
+                    value + min <= rest && value + max >= rest
+                }
+            })
+            .fold(0, |candidates, value| candidates | 1 << value)
+    }
+}
+
+impl<const N: usize> Candidates<N> {
+    const ALL: u32 = (1 << (N + 1)) - 2;
+
+    fn of(cell: (usize, usize)) -> (usize, usize, usize) {
+        let (x, y) = cell;
+        (y, x, Unit::box_of::<N>(cell))
+    }
+
+    pub(crate) fn get(&self, cell: (usize, usize)) -> u32 {
+        let (row, column, r#box) = Self::of(cell);
+        let mut candidates = self.rows[row] & self.columns[column] & self.boxes[r#box];
+        if let Some([main, anti]) = self.diagonals {
+            let (x, y) = cell;
+            if x == y {
+                candidates &= main;
+            }
+            if x + y == N - 1 {
+                candidates &= anti;
+            }
+        }
+        if let Some(cage) = self.cage_of[cell.1][cell.0] {
+            candidates &= self.cages[cage].get(N as u32);
+        }
+
+        candidates
+    }
+
+    fn toggle(&mut self, cell: (usize, usize), value: u8) {
+        let (row, column, r#box) = Self::of(cell);
+        self.rows[row] ^= 1 << value;
+        self.columns[column] ^= 1 << value;
+        self.boxes[r#box] ^= 1 << value;
+        if let Some([main, anti]) = &mut self.diagonals {
+            let (x, y) = cell;
+            if x == y {
+                *main ^= 1 << value;
+            }
+            if x + y == N - 1 {
+                *anti ^= 1 << value;
+            }
+        }
+        if let Some(cage) = self.cage_of[cell.1][cell.0] {
+            let cage = &mut self.cages[cage];
+            if cage.used & 1 << value == 0 {
+                cage.sum += value as u32;
+                cage.empty -= 1;
+            } else {
+                cage.sum -= value as u32;
+                cage.empty += 1;
+            }
+            cage.used ^= 1 << value;
+        }
+    }
+}
+
+impl<const N: usize> Sudoku<N> {
+    // Fills in the empty cells by backtracking, always trying the cell with the fewest candidates
+    // first. Returns `None` if the filled-in cells already break a rule, or if there is no
+    // solution.
+    pub fn solve(&self) -> Option<Self> {
+        let mut solution = self.clone();
+        let mut candidates = self.candidates()?;
+        search(&mut solution.grid, &mut candidates, &mut |_| true).then_some(solution)
+    }
+
+    // Counts the solutions, stopping at `limit`, since a puzzle with few givens can have an
+    // enormous number of them.
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        let mut grid = self.grid;
+        let Some(mut candidates) = self.candidates() else {
+            return 0;
+        };
+
+        let mut count = 0;
+        if limit > 0 {
+            search(&mut grid, &mut candidates, &mut |_| {
+                count += 1;
+                count == limit
+            });
+        }
+
+        count
+    }
+
+    pub fn has_unique_solution(&self) -> bool {
+        self.count_solutions(2) == 1
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.grid.iter().flatten().all(|&value| value != 0)
+    }
+
+    // Whether every cell is filled in, including the given ones, without breaking any rule.
+    pub fn is_solved(&self) -> bool {
+        self.is_complete() && self.candidates().is_some()
+    }
+
+    // Returns `None` if the filled-in cells already break a rule.
+    pub(crate) fn candidates(&self) -> Option<Candidates<N>> {
+        let mut candidates = Candidates {
+            rows: [Candidates::<N>::ALL; N],
+            columns: [Candidates::<N>::ALL; N],
+            boxes: [Candidates::<N>::ALL; N],
+            diagonals: (self.variant == Variant::Diagonal).then_some([Candidates::<N>::ALL; 2]),
+            cages: self
+                .cages
+                .iter()
+                .map(|cage| CageCandidates {
+                    used: 0,
+                    sum: 0,
+                    empty: cage.cells.len(),
+                    target: cage.sum,
+                })
+                .collect(),
+            cage_of: [[None; N]; N],
+        };
+        for (i, cage) in self.cages.iter().enumerate() {
+            for &(x, y) in &cage.cells {
+                candidates.cage_of[y][x] = Some(i);
+            }
+        }
+        for (y, row) in self.grid.iter().enumerate() {
+            for (x, &value) in row.iter().enumerate() {
+                if value == 0 {
+                    continue;
+                }
+                if value as usize > N || candidates.get((x, y)) & 1 << value == 0 {
+                    return None;
+                }
+                candidates.toggle((x, y), value);
+            }
+        }
+
+        Some(candidates)
+    }
+}
+
+// Calls `found` with every solution until it returns true, in which case the grid is left solved
+// and true is returned. Otherwise, the grid is left as it was.
+fn search<const N: usize, F>(
+    grid: &mut [[u8; N]; N],
+    candidates: &mut Candidates<N>,
+    found: &mut F,
+) -> bool
+where
+    F: FnMut(&[[u8; N]; N]) -> bool,
+{
+    let mut best: Option<((usize, usize), u32)> = None;
+    for (y, row) in grid.iter().enumerate() {
+        for (x, &value) in row.iter().enumerate() {
+            if value != 0 {
+                continue;
+            }
+            let cell_candidates = candidates.get((x, y));
+            if best.is_none_or(|(_, best)| cell_candidates.count_ones() < best.count_ones()) {
+                best = Some(((x, y), cell_candidates));
+            }
+        }
+    }
+
+    let Some(((x, y), mut cell_candidates)) = best else {
+        return found(grid);
+    };
+    while cell_candidates != 0 {
+        let value = cell_candidates.trailing_zeros() as u8;
+        cell_candidates &= cell_candidates - 1;
+
+        grid[y][x] = value;
+        candidates.toggle((x, y), value);
+        if search(grid, candidates, found) {
+            return true;
+        }
+        candidates.toggle((x, y), value);
+    }
+    grid[y][x] = 0;
+
+    false
+}
@@ -0,0 +1,97 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{Sudoku, Variant};
+
+// A group of cells that must contain each value exactly once:
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Unit {
+    Row,
+    Column,
+    Box,
+    Diagonal,
+}
+
+impl Unit {
+    // The cells of the unit with the given index in a sudoku of size `N`, counting boxes row by
+    // row, and the main diagonal before the anti-diagonal:
+    pub(crate) fn cells<const N: usize>(
+        self,
+        index: usize,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let b = Sudoku::<N>::BOX;
+        (0..N).map(move |i| match self {
+            Self::Row => (i, index),
+            Self::Column => (index, i),
+            Self::Box => (index % b * b + i % b, index / b * b + i / b),
+            Self::Diagonal if index == 0 => (i, i),
+            Self::Diagonal => (N - 1 - i, i),
+        })
+    }
+
+    // The index of the box of a cell, counting boxes row by row:
+    pub(crate) fn box_of<const N: usize>((x, y): (usize, usize)) -> usize {
+        let b = Sudoku::<N>::BOX;
+        y / b * b + x / b
+    }
+}
+
+// Every cell of a sudoku of size `N`, row by row:
+pub(crate) fn positions<const N: usize>() -> impl Iterator<Item = (usize, usize)> {
+    (0..N * N).map(|cell| (cell % N, cell / N))
+}
+
+impl<const N: usize> Sudoku<N> {
+    // Every cell with its value, row by row:
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), u8)> {
+        positions::<N>().map(|(x, y)| ((x, y), self.grid[y][x]))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = ((usize, usize), u8)>> {
+        (0..N).map(|index| self.unit(Unit::Row, index))
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = ((usize, usize), u8)>> {
+        (0..N).map(|index| self.unit(Unit::Column, index))
+    }
+
+    // The boxes row by row, with the cells of each box row by row:
+    pub fn boxes(&self) -> impl Iterator<Item = impl Iterator<Item = ((usize, usize), u8)>> {
+        (0..N).map(|index| self.unit(Unit::Box, index))
+    }
+
+    // Every unit with its index, including the diagonals if they are constrained:
+    pub(crate) fn units(&self) -> impl Iterator<Item = (Unit, usize)> {
+        let diagonals = if self.variant == Variant::Diagonal {
+            2
+        } else {
+            0
+        };
+        let units = [(Unit::Row, N), (Unit::Column, N), (Unit::Box, N)];
+        units
+            .into_iter()
+            .chain([(Unit::Diagonal, diagonals)])
+            .flat_map(|(unit, count)| (0..count).map(move |index| (unit, index)))
+    }
+
+    // The units that a cell is in:
+    pub(crate) fn units_of(&self, cell: (usize, usize)) -> impl Iterator<Item = (Unit, usize)> {
+        self.units()
+            .filter(move |&(unit, index)| unit.cells::<N>(index).any(|other| other == cell))
+    }
+
+    fn unit(&self, unit: Unit, index: usize) -> impl Iterator<Item = ((usize, usize), u8)> {
+        unit.cells::<N>(index)
+            .map(|(x, y)| ((x, y), self.grid[y][x]))
+    }
+}
+
+impl Display for Unit {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Row => write!(f, "row"),
+            Self::Column => write!(f, "column"),
+            Self::Box => write!(f, "box"),
+            Self::Diagonal => write!(f, "diagonal"),
+        }
+    }
+}
@@ -0,0 +1,146 @@
+mod common;
+
+use graph::Graph;
+use sudoku::{Cage, PUZZLE, Sudoku, SudokuBuilder, SudokuError};
+
+use common::{complete_coloring, uncolored};
+
+fn cage(cells: &[(usize, usize)], sum: u32) -> Cage {
+    Cage {
+        cells: cells.into(),
+        sum,
+    }
+}
+
+// The solution of `PUZZLE`, with every cell given:
+fn solved() -> Sudoku {
+    let solution = PUZZLE.solve().expect("the puzzle has a solution");
+    Sudoku {
+        given: (0..9).flat_map(|y| (0..9).map(move |x| (x, y))).collect(),
+        ..solution
+    }
+}
+
+#[test]
+fn correct_sum() {
+    // The first row of the solution starts with 4, 5, and 7, which add up to 16 in many ways:
+    let sudoku = solved()
+        .with_cages([cage(&[(0, 0), (1, 0), (2, 0)], 16)])
+        .expect("the cage is valid");
+    assert!(sudoku.is_solved());
+
+    let graph = Graph::from(&sudoku);
+    assert!(graph.nodes.len() > 81 + 9);
+    assert!(!graph.nodes.contains(&0));
+    assert!(graph.is_properly_colored(&0));
+    assert!(complete_coloring(&uncolored(&graph, 9), 9).is_some());
+}
+
+#[test]
+fn wrong_sum() {
+    // 4, 5, and 7 are values of sets adding up to 17, such as 1, 7, 9 and 3, 5, 9, but not together:
+    let sudoku = solved()
+        .with_cages([cage(&[(0, 0), (1, 0), (2, 0)], 17)])
+        .expect("the cage is valid");
+    assert!(!sudoku.is_solved());
+
+    let graph = Graph::from(&sudoku);
+    assert!(!graph.is_properly_colored(&0));
+    assert!(complete_coloring(&uncolored(&graph, 9), 9).is_none());
+}
+
+#[test]
+fn impossible_sum() {
+    let sudoku = solved()
+        .with_cages([cage(&[(0, 0), (1, 0)], 2)])
+        .expect("the cage is valid");
+
+    let graph = Graph::from(&sudoku);
+    assert!(complete_coloring(&uncolored(&graph, 9), 9).is_none());
+}
+
+#[test]
+fn unique_set() {
+    // 4 and 5 are the only values of two cells adding up to 9 other than 1 and 8, 2 and 7, and
+    // 3 and 6, so this encodes the sum with a gadget, while 3 cells adding up to 6 don't need one:
+    let sudoku = solved()
+        .with_cages([cage(&[(0, 0), (1, 0)], 9)])
+        .expect("the cage is valid");
+    let graph = Graph::from(&sudoku);
+    assert!(graph.is_properly_colored(&0));
+    assert!(complete_coloring(&uncolored(&graph, 9), 9).is_some());
+
+    let sudoku = solved()
+        .with_cages([cage(&[(0, 0), (1, 0), (2, 0)], 6)])
+        .expect("the cage is valid");
+    let graph = Graph::from(&sudoku);
+    assert_eq!(graph.nodes.len(), 81 + 9);
+    assert!(complete_coloring(&uncolored(&graph, 9), 9).is_none());
+}
+
+#[test]
+fn solve_with_cages() {
+    // A cage on empty cells of the puzzle, consistent with its solution, which the coloring has to
+    // find values for:
+    let solution = PUZZLE.solve().expect("the puzzle has a solution");
+    let cells = [(1, 0), (2, 0), (3, 0)];
+    let sum = cells.iter().map(|&(x, y)| solution.grid[y][x] as u32).sum();
+    let puzzle = PUZZLE
+        .clone()
+        .with_cages([cage(&cells, sum)])
+        .expect("the cage is valid");
+
+    let graph = Graph::from(&puzzle);
+    let colors = complete_coloring(&graph, 9).expect("the puzzle can be colored");
+    let graph = Graph {
+        nodes: colors,
+        edges: graph.edges,
+    };
+    let found = Sudoku::from_coloring(&graph, &puzzle.given).expect("the coloring is anchored");
+    assert_eq!(found.grid, solution.grid);
+}
+
+#[test]
+fn invalid_cages() {
+    // Three rows of cells, more than there are values:
+    let rows: Vec<_> = (0..3).flat_map(|y| (0..9).map(move |x| (x, y))).collect();
+    assert!(matches!(
+        PUZZLE.clone().with_cages([cage(&rows, 135)]),
+        Err(SudokuError::CageTooLarge { cells: 27 })
+    ));
+    assert!(matches!(
+        PUZZLE.clone().with_cages([cage(&[(0, 0), (0, 0)], 3)]),
+        Err(SudokuError::DuplicateCageCell { cell: (0, 0) })
+    ));
+    assert!(matches!(
+        PUZZLE.clone().with_cages([cage(&[(9, 0)], 1)]),
+        Err(SudokuError::OutOfBounds { cell: (9, 0) })
+    ));
+    assert!(matches!(
+        PUZZLE
+            .clone()
+            .with_cages([cage(&[(0, 0), (1, 0)], 3), cage(&[(1, 0), (2, 0)], 3)]),
+        Err(SudokuError::OverlappingCages { cell: (1, 0) })
+    ));
+
+    let mut builder = SudokuBuilder::<9>::new();
+    assert!(matches!(
+        builder.add_cage(cage(&rows, 135)),
+        Err(SudokuError::CageTooLarge { cells: 27 })
+    ));
+    assert!(matches!(
+        builder.add_cage(cage(&[(0, 0), (0, 0)], 3)),
+        Err(SudokuError::DuplicateCageCell { cell: (0, 0) })
+    ));
+}
+
+#[test]
+fn solve_with_oversized_cage() {
+    // Cages can still be set directly, so the solver has to cope with one that is too large:
+    let rows: Vec<_> = (0..3).flat_map(|y| (0..9).map(move |x| (x, y))).collect();
+    let puzzle = Sudoku {
+        cages: Box::new([cage(&rows, 135)]),
+        ..PUZZLE.clone()
+    };
+    assert!(puzzle.solve().is_none());
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use graph::Graph;
+use sudoku::{PUZZLE, Sudoku};
+
+use common::{complete_coloring, uncolored};
+
+// Colors the graph of `PUZZLE` and reads the grid back from the coloring, after applying
+// `permute` to every color.
+fn round_trip(graph: &Graph<u8>, permute: impl Fn(u8) -> u8) -> Sudoku {
+    let colors = complete_coloring(graph, 9).expect("the puzzle can be colored");
+    let graph = Graph {
+        nodes: colors.iter().map(|&color| permute(color)).collect(),
+        edges: graph.edges.clone(),
+    };
+    assert!(graph.is_properly_colored(&0));
+    Sudoku::from_coloring(&graph, &PUZZLE.given).expect("the coloring is anchored")
+}
+
+#[test]
+fn reduce_color_recover() {
+    let solution = PUZZLE.solve().expect("the puzzle has a solution");
+    let graph = Graph::from(&*PUZZLE);
+
+    let found = round_trip(&graph, |color| color);
+    assert!(found.is_solved());
+    assert_eq!(found.grid, solution.grid);
+    assert_eq!(found.given, PUZZLE.given);
+}
+
+#[test]
+fn permuted_colors() {
+    let solution = PUZZLE.solve().expect("the puzzle has a solution");
+    let graph = Graph::from(&*PUZZLE);
+
+    let found = round_trip(&graph, |color| 10 - color);
+    assert!(found.is_solved());
+    assert_eq!(found.grid, solution.grid);
+}
+
+#[test]
+fn givens_from_edges() {
+    // The givens are kept by the edges to the constraint nodes, not by the colors of their cells:
+    let solution = PUZZLE.solve().expect("the puzzle has a solution");
+    let graph = uncolored(&Graph::from(&*PUZZLE), 9);
+
+    let found = round_trip(&graph, |color| color % 9 + 1);
+    assert!(found.is_solved());
+    assert_eq!(found.grid, solution.grid);
+}
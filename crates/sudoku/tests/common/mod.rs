@@ -0,0 +1,60 @@
+use graph::Graph;
+
+// Colors the uncolored nodes of a graph with colors from 1 to `colors`, keeping the colors of the
+// other nodes, by backtracking over the node with the fewest colors left, and of those the one with
+// the most uncolored neighbors. Returns `None` if there is no such coloring.
+pub fn complete_coloring(graph: &Graph<u8>, colors: u8) -> Option<Box<[u8]>> {
+    if graph.violations(&0).next().is_some() {
+        return None;
+    }
+
+    let adjacency = graph.adjacency();
+    let mut nodes = graph.nodes.to_vec();
+    let all = (1u32 << (colors + 1)) - 2;
+
+    fn search(adjacency: &graph::Adjacency, nodes: &mut [u8], all: u32) -> bool {
+        let mut best: Option<(usize, u32, (u32, usize))> = None;
+        for node in (0..nodes.len()).filter(|&node| nodes[node] == 0) {
+            let neighbors = adjacency.neighbors(node);
+            let taken = neighbors
+                .iter()
+                .fold(0, |taken, &neighbor| taken | 1 << nodes[neighbor]);
+            let options = all & !taken;
+            if options == 0 {
+                return false;
+            }
+            let uncolored = neighbors.iter().filter(|&&n| nodes[n] == 0).count();
+            let rank = (options.count_ones(), usize::MAX - uncolored);
+            if best.is_none_or(|(_, _, best)| rank < best) {
+                best = Some((node, options, rank));
+            }
+        }
+
+        let Some((node, options, _)) = best else {
+            return true;
+        };
+        for color in (1..u32::BITS).filter(|color| options & 1 << color != 0) {
+            nodes[node] = color as u8;
+            if search(adjacency, nodes, all) {
+                return true;
+            }
+        }
+        nodes[node] = 0;
+        false
+    }
+
+    search(&adjacency, &mut nodes, all).then(|| nodes.into())
+}
+
+// Clears the colors of every node but the constraint nodes, which anchor each color to a value,
+// so that only the structure of the graph decides whether it can be colored.
+pub fn uncolored(graph: &Graph<u8>, size: usize) -> Graph<u8> {
+    let constraints = size * size..size * size + size;
+    let mut graph = graph.clone();
+    for (node, color) in graph.nodes.iter_mut().enumerate() {
+        if !constraints.contains(&node) {
+            *color = 0;
+        }
+    }
+    graph
+}
@@ -0,0 +1,40 @@
+use sudoku::{PUZZLE, Sudoku};
+
+// Follows the hints from the puzzle, checking each against the next step of solving it logically
+// from the same cells, until the hints run out.
+#[test]
+fn hints_follow_logic() {
+    let mut sudoku: Sudoku = PUZZLE.clone();
+    let mut hints = 0;
+    while let Some(hint) = sudoku.hint() {
+        let steps = match sudoku.solve_logically() {
+            Ok((_, steps)) => steps,
+            Err(stuck) => stuck.steps,
+        };
+        let step = &steps[0];
+        assert_eq!((step.cell, step.value), (hint.cell, Some(hint.value)));
+        assert_eq!(step.technique, hint.technique);
+
+        let (x, y) = hint.cell;
+        sudoku.grid[y][x] = hint.value;
+        hints += 1;
+    }
+
+    assert!(hints > 0);
+    if sudoku.is_complete() {
+        assert!(sudoku.is_solved());
+    }
+}
+
+#[test]
+fn no_hint_for_broken_rules() {
+    // Repeats a given value in an empty cell of its row:
+    let mut sudoku: Sudoku = PUZZLE.clone();
+    let (x, y) = PUZZLE.given[0];
+    let i = (0..9)
+        .find(|&i| sudoku.grid[y][i] == 0)
+        .expect("the row has an empty cell");
+    sudoku.grid[y][i] = sudoku.grid[y][x];
+
+    assert_eq!(sudoku.hint(), None);
+}
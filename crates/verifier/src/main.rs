@@ -1,24 +1,37 @@
 use std::{
+    env,
     error::Error,
     fmt::{self, Display, Formatter},
     io::{self, Write},
-    thread,
+    process, thread,
     time::Duration,
 };
 
-use bytes::Bytes;
-use graph::{Edge, EncryptedNode, Graph};
+use bytes::{Bytes, Envelope, ReadLimits};
+use graph::{
+    CommitmentContext, Graph, LabeledEdge, MerkleHash, MerkleProof, Message, Opening, SessionId,
+};
 use rand::prelude::*;
+use sudoku::{Constraint, Sudoku, library};
 
 fn main() {
-    let graph = Graph::from(&*sudoku::PUZZLE);
-    let mut edges = graph.edges.clone();
+    // The puzzle has to be the same as the prover's, so it is chosen by the same argument.
+    let puzzle = match env::args().nth(1) {
+        Some(id) if let Some(puzzle) = library::get(&id) => puzzle,
+        Some(path) => Sudoku::load(&path).unwrap_or_else(|err| {
+            eprintln!("Could not load {path}: {err}");
+            process::exit(1)
+        }),
+        None => sudoku::PUZZLE.clone(),
+    };
+    let mut edges = puzzle.labeled_edges();
+    let node_count = Graph::from(&puzzle).nodes.len();
 
     loop {
         print!("Verifying");
         io::stdout().flush().expect("flush should succeed");
 
-        match verify(&mut edges) {
+        match verify(&mut edges, node_count) {
             Ok(()) => println!(" - Solved"),
             Err(err) => println!(" - {err}"),
         }
@@ -27,42 +40,69 @@ fn main() {
     }
 }
 
-fn verify(edges: &mut Box<[Edge]>) -> Result<(), Box<dyn Error>> {
-    let encrypted_node_bytes: Vec<u8> = ureq::get("http://127.0.0.1:8000/nodes")
-        .call()?
-        .body_mut()
-        .read_to_vec()?;
-    let encrypted_nodes: Vec<Box<[EncryptedNode]>> = Bytes::from_bytes(&encrypted_node_bytes)?;
+fn verify(edges: &mut [LabeledEdge<Constraint>], node_count: usize) -> Result<(), Box<dyn Error>> {
+    // The responses come from the prover, so no collection in them may be longer than the number
+    // of rounds, which is one per edge:
+    let limits = ReadLimits {
+        max_len: edges.len(),
+        ..Default::default()
+    };
+
+    let mut root_response = ureq::get("http://127.0.0.1:8000/nodes")
+        .query("count", edges.len().to_string())
+        .call()?;
+    let Envelope((session, roots)): Message<(SessionId, Vec<MerkleHash>)> =
+        Bytes::read_from_with_limits(root_response.body_mut().as_reader(), limits)?;
+
+    // Every edge is challenged in a round of its own, so the prover has to commit to exactly as
+    // many rounds as were asked for:
+    if roots.len() != edges.len() {
+        return Err(VerificationError::RoundCountMismatch.into());
+    }
 
     let mut rng = rand::rng();
     edges.shuffle(&mut rng);
 
-    let verification_data_bytes: Vec<u8> = ureq::post("http://127.0.0.1:8000/verify")
-        .send(&*edges.to_bytes())?
-        .body_mut()
-        .read_to_vec()?;
+    let message: Message<_> = Envelope(edges.iter().map(|edge| edge.edge).collect::<Vec<_>>());
+    let mut verification_response =
+        ureq::post("http://127.0.0.1:8000/verify").send(&*message.to_bytes())?;
 
-    let Ok(verification_data) = <Vec<((u8, u8), (u64, u64))>>::from_bytes(&verification_data_bytes)
-    else {
+    type VerificationData = ((Opening, Opening), (MerkleProof, MerkleProof));
+    let Ok(Envelope(verification_data)) = <Message<Vec<VerificationData>>>::read_from_with_limits(
+        verification_response.body_mut().as_reader(),
+        limits,
+    ) else {
         return Err(VerificationError::InvalidVerificationData.into());
     };
 
-    for (i, (values, keys)) in verification_data.into_iter().enumerate() {
-        let edge = edges[i];
+    // Rounds without verification data would otherwise go unchecked:
+    if verification_data.len() != roots.len() {
+        return Err(VerificationError::RoundCountMismatch.into());
+    }
+
+    for (i, (openings, proofs)) in verification_data.into_iter().enumerate() {
+        let LabeledEdge { edge, label } = edges[i];
+        let (a, b) = edge.indices();
+        let context = CommitmentContext {
+            session,
+            round: i as u64,
+        };
 
-        if values.0 == 0 || values.1 == 0 {
+        if openings.0.value == 0 || openings.1.value == 0 {
             return Err(VerificationError::Unsolved.into());
         }
 
-        if values.0 == values.1 {
-            return Err(VerificationError::UnsatisfiedConstraint.into());
+        if openings.0.value == openings.1.value {
+            return Err(VerificationError::UnsatisfiedConstraint(label).into());
         }
 
-        if encrypted_nodes[i][edge.0] != graph::hash(values.0, keys.0) {
+        let encrypted_node = openings.0.commitment(&context);
+        if !graph::verify_merkle_opening(&roots[i], node_count, a, &encrypted_node, &proofs.0) {
             return Err(VerificationError::IncorrectHash.into());
         }
 
-        if encrypted_nodes[i][edge.1] != graph::hash(values.1, keys.1) {
+        let encrypted_node = openings.1.commitment(&context);
+        if !graph::verify_merkle_opening(&roots[i], node_count, b, &encrypted_node, &proofs.1) {
             return Err(VerificationError::IncorrectHash.into());
         }
     }
@@ -74,7 +114,8 @@ fn verify(edges: &mut Box<[Edge]>) -> Result<(), Box<dyn Error>> {
 enum VerificationError {
     IncorrectHash,
     InvalidVerificationData,
-    UnsatisfiedConstraint,
+    RoundCountMismatch,
+    UnsatisfiedConstraint(Constraint),
     Unsolved,
 }
 
@@ -85,7 +126,10 @@ impl Display for VerificationError {
         match self {
             Self::IncorrectHash => write!(f, "Incorrect hash"),
             Self::InvalidVerificationData => write!(f, "Invalid verification data"),
-            Self::UnsatisfiedConstraint => write!(f, "Unsatisfied constraint"),
+            Self::RoundCountMismatch => write!(f, "Round count mismatch"),
+            Self::UnsatisfiedConstraint(constraint) => {
+                write!(f, "Unsatisfied {constraint} constraint")
+            }
             Self::Unsolved => write!(f, "Unsolved"),
         }
     }